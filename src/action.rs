@@ -1,3 +1,8 @@
+use crate::components::examination::QuestionType;
+use crate::i18n;
+use crate::server::protocol::Paper;
+use chrono::{DateTime, Local};
+use examination::engine::{AuditEvent, QuestionTime};
 use serde::{Deserialize, Serialize};
 use strum::Display;
 
@@ -15,6 +20,97 @@ pub enum Action {
     Submit,
     Alert(String, ConfirmEvent),
     Confirm(ConfirmEvent),
+    ToggleExplanation,
+    ToggleFlag,
+    /// 下一题，在交卷汇总中为下一条
+    NextQuestion,
+    /// 上一题，在交卷汇总中为上一条
+    PrevQuestion,
+    NextTab,
+    PrevTab,
+    /// 打开作答弹框，复习模式下为作答或接受建议评分
+    OpenAnswer,
+    /// 复习模式下直接查看答案
+    RevealAnswer,
+    /// 开始输入题号跳转
+    StartJump,
+    /// 从交卷汇总跳转到选中的题目
+    JumpToSelected,
+    /// 确认当前弹框：提交答案、确认跳转、确认交卷等
+    ConfirmDialog,
+    /// 关闭当前弹框
+    CancelDialog,
+    /// 填空题切换到下一个输入框
+    NextInput,
+    NextOption,
+    PrevOption,
+    /// 选中/取消当前高亮的选项
+    ToggleOption,
+    AnswerYes,
+    AnswerNo,
+    /// 放弃作答弹框中未确认的输入，例如题目超时
+    DiscardInput,
+    /// 切换按顺序排列全部题目的纯文本视图
+    ToggleLinear,
+    /// 某道题的作答内容发生变化
+    AnswerChanged(QuestionType, usize, Vec<Option<String>>),
+    /// 开始浏览某道题
+    Viewing(QuestionType, usize),
+    /// 已交卷，附带每道题的用时
+    Submitted(Vec<QuestionTime>),
+    /// 新的审计事件，联网考试中同步给服务端
+    Audited(AuditEvent),
+    /// 联网考试中把交卷请求发给服务端
+    RemoteSubmit,
+    /// 服务端判分后返回的得分与带答案的试卷
+    Graded(u16, Paper),
+    /// 考试截止时间被监考老师修改
+    Deadline(DateTime<Local>),
+    /// 监考面板：延长考试时间
+    ExtendTime,
+    /// 监考面板：开始输入广播消息
+    StartBroadcast,
+    /// 监考面板：为所有未交卷的考生收卷
+    SubmitAll,
+    /// 监考面板：收卷后停止考试服务并退出
+    StopExam,
+}
+
+impl Action {
+    /// 帮助面板中展示的动作说明（当前语言），内部动作返回 None
+    pub fn description(&self) -> Option<&'static str> {
+        let key = match self {
+            Action::Quit => "action.quit",
+            Action::Suspend => "action.suspend",
+            Action::Help => "action.help",
+            Action::Submit => "action.submit",
+            Action::ToggleExplanation => "action.toggle_explanation",
+            Action::ToggleFlag => "action.toggle_flag",
+            Action::NextQuestion => "action.next_question",
+            Action::PrevQuestion => "action.prev_question",
+            Action::NextTab => "action.next_tab",
+            Action::PrevTab => "action.prev_tab",
+            Action::OpenAnswer => "action.open_answer",
+            Action::RevealAnswer => "action.reveal_answer",
+            Action::StartJump => "action.start_jump",
+            Action::JumpToSelected => "action.jump_to_selected",
+            Action::ConfirmDialog => "action.confirm_dialog",
+            Action::CancelDialog => "action.cancel_dialog",
+            Action::NextInput => "action.next_input",
+            Action::NextOption => "action.next_option",
+            Action::PrevOption => "action.prev_option",
+            Action::ToggleOption => "action.toggle_option",
+            Action::AnswerYes => "action.answer_yes",
+            Action::AnswerNo => "action.answer_no",
+            Action::ToggleLinear => "action.toggle_linear",
+            Action::ExtendTime => "action.extend_time",
+            Action::StartBroadcast => "action.start_broadcast",
+            Action::SubmitAll => "action.submit_all",
+            Action::StopExam => "action.stop_exam",
+            _ => return None,
+        };
+        Some(i18n::t(key))
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Display, Serialize, Deserialize)]
@@ -22,4 +118,10 @@ pub enum ConfirmEvent {
     Nothing,
     Submit,
     Score,
+    /// 监考老师确认强制收卷
+    SubmitAll,
+    /// 监考老师确认在仍有考生在线时收卷并退出
+    StopExam,
+    /// 确认挂起
+    Suspend,
 }
@@ -0,0 +1,471 @@
+//! HTTP JSON 接口：不启动终端界面，供其他系统列出考试、生成试卷、提交作答并获取成绩。
+//!
+//! - `GET /exams`：所有考试配置
+//! - `POST /exams/{id}/papers`：按考试配置抽题组卷，返回不含答案的试卷
+//! - `PUT /papers/{id}/answers`：提交若干道题的作答内容，交卷前可多次提交；作答按终端界面的规则校验并规范化，不合法时返回 400 且整批都不保存
+//! - `POST /papers/{id}/submit`：交卷并判分，返回成绩报告
+//! - `GET /papers/{id}/report`：交卷后再次获取成绩报告
+//!
+//! 题目以 [`QuestionEnum::id`] 标识，同一份试卷中题干与选项相同的题目只保留一道，保证标识唯一。
+//! 判分与终端界面、局域网考试使用同样的逻辑。
+//! 成绩报告带有 `signature` 字段，保存后可用 `examination verify` 校验是否被修改。
+
+use crate::components::examination::{
+    self, Examination, ExaminationConfig, QuestionEnum, QuestionType,
+};
+use crate::config::Config;
+use crate::i18n;
+use crate::server::protocol::Paper;
+use ::examination::engine::paper::{self, without_answers};
+use ::examination::engine::{AnswerKey, InvalidInput, Signed, SigningKey};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post, put};
+use axum::{Json, Router};
+use chrono::{DateTime, Local, TimeDelta};
+use color_eyre::Result;
+use linked_hash_map::LinkedHashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use tracing::warn;
+
+/// `examination.json` 对应的考试编号
+const DEFAULT_EXAM: &str = "default";
+
+/// 一份考试配置，`id` 用于生成试卷
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExamProfile {
+    pub id: String,
+    #[serde(flatten)]
+    pub exam: ExaminationConfig,
+}
+
+/// 试卷中的一道题，交卷前不含答案与解析
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PaperQuestion {
+    pub id: String,
+    pub question_type: QuestionType,
+    pub question: QuestionEnum,
+    /// 交卷后的得分
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<u16>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PaperResponse {
+    pub id: String,
+    pub exam: String,
+    pub deadline: DateTime<Local>,
+    pub questions: Vec<PaperQuestion>,
+}
+
+/// 一道题的作答内容，填空题与材料题按输入框顺序排列
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Answer {
+    pub id: String,
+    pub user_input: Vec<Option<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Report {
+    pub id: String,
+    pub exam: String,
+    pub score: u16,
+    pub full_score: u16,
+    pub submitted_at: DateTime<Local>,
+    pub questions: Vec<PaperQuestion>,
+}
+
+/// 接口返回的错误
+pub struct ApiError(StatusCode, String);
+
+impl ApiError {
+    fn not_found() -> Self {
+        Self(StatusCode::NOT_FOUND, i18n::t("api.not_found").to_string())
+    }
+
+    fn conflict(key: &str) -> Self {
+        Self(StatusCode::CONFLICT, i18n::t(key).to_string())
+    }
+
+    fn invalid_answer(e: &InvalidInput) -> Self {
+        let error = examination::input_error_text(&e.error);
+        Self(
+            StatusCode::BAD_REQUEST,
+            i18n::tf("serve.invalid_answer", &[("error", &error)]),
+        )
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = Json(serde_json::json!({ "error": self.1 }));
+        (self.0, body).into_response()
+    }
+}
+
+struct Sheet {
+    exam: String,
+    /// 带答案的完整试卷
+    paper: Paper,
+    deadline: DateTime<Local>,
+    submitted_at: Option<DateTime<Local>>,
+}
+
+impl Sheet {
+    fn questions(&self, redact: bool) -> Vec<PaperQuestion> {
+        let paper = if redact {
+            without_answers(&self.paper)
+        } else {
+            self.paper.clone()
+        };
+        paper
+            .into_iter()
+            .flat_map(|(question_type, questions)| {
+                questions.into_iter().map(move |question| PaperQuestion {
+                    id: question.id(),
+                    question_type,
+                    score: (!redact).then(|| question.cal_score()),
+                    question,
+                })
+            })
+            .collect()
+    }
+
+    fn report(&self, id: &str) -> Option<Report> {
+        Some(Report {
+            id: id.to_string(),
+            exam: self.exam.clone(),
+            score: paper::score(&self.paper),
+            full_score: paper::full_score(&self.paper),
+            submitted_at: self.submitted_at?,
+            questions: self.questions(false),
+        })
+    }
+}
+
+pub struct Api {
+    profiles: LinkedHashMap<String, ExaminationConfig>,
+    bank: HashMap<QuestionType, Vec<QuestionEnum>>,
+    papers: Mutex<HashMap<String, Sheet>>,
+    /// 成绩报告的签名密钥
+    signing_key: SigningKey,
+}
+
+/// 读取所有考试配置：`examination.json` 以及 `exams` 目录下的 `<编号>.json`
+pub fn load_profiles(config: &Config) -> LinkedHashMap<String, ExaminationConfig> {
+    let mut profiles = LinkedHashMap::new();
+    profiles.insert(DEFAULT_EXAM.to_string(), Examination::load(config.clone()));
+    let mut paths = fs::read_dir(config.config.data_dir.join("exams"))
+        .map(|dir| {
+            dir.flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|e| e == "json"))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    paths.sort();
+    for path in paths {
+        let Some(id) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+            continue;
+        };
+        let exam = fs::read(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|ec| serde_json::from_slice(&ec).map_err(|e| e.to_string()));
+        match exam {
+            Ok(exam) => {
+                profiles.insert(id, exam);
+            }
+            Err(e) => warn!("Failed to load {}: {e}", path.display()),
+        }
+    }
+    profiles
+}
+
+/// 读取考试配置与题库，在指定地址上提供 HTTP 接口
+pub async fn serve(
+    config: &Config,
+    addr: &str,
+    key: Option<&AnswerKey>,
+    signing_key: SigningKey,
+) -> Result<()> {
+    let mut bank = examination::load_bank(config);
+    // 题库不含答案时换回答案，由服务端判分
+    if let Some(key) = key {
+        bank = key.restore_bank(&bank);
+    }
+    let api = Arc::new(Api::new(load_profiles(config), bank, signing_key));
+    let listener = TcpListener::bind(addr).await?;
+    println!(
+        "{}",
+        i18n::tf("api.listening", &[("addr", &listener.local_addr()?)])
+    );
+    axum::serve(listener, api.router()).await?;
+    Ok(())
+}
+
+impl Api {
+    pub fn new(
+        profiles: LinkedHashMap<String, ExaminationConfig>,
+        bank: HashMap<QuestionType, Vec<QuestionEnum>>,
+        signing_key: SigningKey,
+    ) -> Self {
+        Self {
+            profiles,
+            bank,
+            papers: Mutex::new(HashMap::new()),
+            signing_key,
+        }
+    }
+
+    fn sign(&self, report: Report) -> Result<Json<Signed<Report>>, ApiError> {
+        self.signing_key
+            .sign(report)
+            .map(Json)
+            .map_err(|e| ApiError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+    }
+
+    pub fn router(self: Arc<Self>) -> Router {
+        Router::new()
+            .route("/exams", get(list_exams))
+            .route("/exams/{id}/papers", post(new_paper))
+            .route("/papers/{id}/answers", put(answer))
+            .route("/papers/{id}/submit", post(submit))
+            .route("/papers/{id}/report", get(report))
+            .with_state(self)
+    }
+}
+
+async fn list_exams(State(api): State<Arc<Api>>) -> Json<Vec<ExamProfile>> {
+    Json(
+        api.profiles
+            .iter()
+            .map(|(id, exam)| ExamProfile {
+                id: id.clone(),
+                exam: exam.clone(),
+            })
+            .collect(),
+    )
+}
+
+async fn new_paper(
+    State(api): State<Arc<Api>>,
+    Path(exam_id): Path<String>,
+) -> Result<Json<PaperResponse>, ApiError> {
+    let exam = api.profiles.get(&exam_id).ok_or_else(ApiError::not_found)?;
+    let sheet = Sheet {
+        exam: exam_id,
+        paper: unique_questions(QuestionEnum::choose(&api.bank, exam).into_iter().collect()),
+        deadline: Local::now() + TimeDelta::seconds(exam.duration() as i64),
+        submitted_at: None,
+    };
+    let id = format!("{:016x}", rand::random::<u64>());
+    let response = PaperResponse {
+        id: id.clone(),
+        exam: sheet.exam.clone(),
+        deadline: sheet.deadline,
+        questions: sheet.questions(true),
+    };
+    api.papers.lock().unwrap().insert(id, sheet);
+    Ok(Json(response))
+}
+
+/// 去掉标识重复的题目，否则一份作答会同时记到几道题上
+fn unique_questions(paper: Paper) -> Paper {
+    let mut ids = HashSet::new();
+    paper
+        .into_iter()
+        .map(|(question_type, questions)| {
+            let questions = questions
+                .into_iter()
+                .filter(|q| {
+                    let unique = ids.insert(q.id());
+                    if !unique {
+                        warn!("Skipped a duplicate question: {}", q.id());
+                    }
+                    unique
+                })
+                .collect();
+            (question_type, questions)
+        })
+        .collect()
+}
+
+async fn answer(
+    State(api): State<Arc<Api>>,
+    Path(id): Path<String>,
+    Json(answers): Json<Vec<Answer>>,
+) -> Result<StatusCode, ApiError> {
+    let mut papers = api.papers.lock().unwrap();
+    let sheet = papers.get_mut(&id).ok_or_else(ApiError::not_found)?;
+    if sheet.submitted_at.is_some() {
+        return Err(ApiError::conflict("serve.submitted"));
+    }
+    if Local::now() > sheet.deadline {
+        return Err(ApiError::conflict("serve.time_up"));
+    }
+    // 先检查所有题目都存在且作答合法，避免只保存了一部分作答
+    let mut questions = sheet
+        .paper
+        .iter_mut()
+        .flat_map(|(_, questions)| questions)
+        .map(|q| (q.id(), q))
+        .collect::<HashMap<_, _>>();
+    let mut normalized = vec![];
+    for Answer { id, user_input } in answers {
+        let question = questions.get(&id).ok_or_else(|| {
+            ApiError(
+                StatusCode::BAD_REQUEST,
+                i18n::t("serve.invalid_question").to_string(),
+            )
+        })?;
+        let user_input = question
+            .normalize_input(&user_input)
+            .map_err(|e| ApiError::invalid_answer(&e))?;
+        normalized.push((id, user_input));
+    }
+    for (id, user_input) in normalized {
+        if let Some(question) = questions.get_mut(&id) {
+            question.set_user_input(user_input);
+        }
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn submit(
+    State(api): State<Arc<Api>>,
+    Path(id): Path<String>,
+) -> Result<Json<Signed<Report>>, ApiError> {
+    let mut papers = api.papers.lock().unwrap();
+    let sheet = papers.get_mut(&id).ok_or_else(ApiError::not_found)?;
+    if sheet.submitted_at.is_some() {
+        return Err(ApiError::conflict("serve.submitted"));
+    }
+    sheet.submitted_at = Some(Local::now());
+    api.sign(sheet.report(&id).ok_or_else(ApiError::not_found)?)
+}
+
+async fn report(
+    State(api): State<Arc<Api>>,
+    Path(id): Path<String>,
+) -> Result<Json<Signed<Report>>, ApiError> {
+    let papers = api.papers.lock().unwrap();
+    let sheet = papers.get(&id).ok_or_else(ApiError::not_found)?;
+    let report = sheet
+        .report(&id)
+        .ok_or_else(|| ApiError::conflict("api.not_submitted"))?;
+    api.sign(report)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::api::{Api, PaperResponse, Report, unique_questions};
+    use crate::components::examination::QuestionType;
+    use crate::fixture;
+    use ::examination::engine::{Bank, SigningKey};
+    use linked_hash_map::LinkedHashMap;
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// 发送一个 HTTP/1.1 请求，返回状态码与响应体
+    async fn request(addr: &str, method: &str, path: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let request = format!(
+            "{method} {path} HTTP/1.1\r\nHost: {addr}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (status, body.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_api() {
+        let mut profiles = LinkedHashMap::new();
+        profiles.insert("quiz".to_string(), fixture::exam());
+        let key = SigningKey::generate();
+        let api = Arc::new(Api::new(profiles, fixture::bank(), key.clone()));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move { axum::serve(listener, api.router()).await });
+
+        let (status, body) = request(&addr, "GET", "/exams", "").await;
+        assert_eq!(status, 200);
+        assert!(body.contains(r#""id":"quiz""#));
+        assert_eq!(
+            request(&addr, "POST", "/exams/nope/papers", "").await.0,
+            404
+        );
+
+        let (status, body) = request(&addr, "POST", "/exams/quiz/papers", "").await;
+        assert_eq!(status, 200);
+        // 试卷中不含答案与解析
+        assert!(!body.contains("2 < 3"));
+        let paper: PaperResponse = serde_json::from_str(&body).unwrap();
+        let id_of = |question_type| {
+            paper
+                .questions
+                .iter()
+                .find(|q| q.question_type == question_type)
+                .unwrap()
+                .id
+                .clone()
+        };
+        let answers = format!(
+            r#"[{{"id":"{}","user_input":["b"]}},{{"id":"{}","user_input":["y"]}}]"#,
+            id_of(QuestionType::SingleSelect),
+            id_of(QuestionType::Judge)
+        );
+        let answers_path = format!("/papers/{}/answers", paper.id);
+        let report_path = format!("/papers/{}/report", paper.id);
+        let bad = r#"[{"id":"missing","user_input":["B"]}]"#;
+        assert_eq!(request(&addr, "PUT", &answers_path, bad).await.0, 400);
+        // 作答与终端界面一样校验，不合法时整批都不保存
+        let invalid = format!(
+            r#"[{{"id":"{}","user_input":["A"]}},{{"id":"{}","user_input":["maybe"]}}]"#,
+            id_of(QuestionType::SingleSelect),
+            id_of(QuestionType::Judge)
+        );
+        assert_eq!(request(&addr, "PUT", &answers_path, &invalid).await.0, 400);
+        assert_eq!(request(&addr, "PUT", &answers_path, &answers).await.0, 204);
+        assert_eq!(request(&addr, "GET", &report_path, "").await.0, 409);
+
+        let (status, body) =
+            request(&addr, "POST", &format!("/papers/{}/submit", paper.id), "").await;
+        assert_eq!(status, 200);
+        let report: Report = serde_json::from_str(&body).unwrap();
+        assert_eq!((report.score, report.full_score), (2, 3));
+        assert!(body.contains("2 < 3"));
+
+        let (status, body) = request(&addr, "GET", &report_path, "").await;
+        assert_eq!(status, 200);
+        assert_eq!(serde_json::from_str::<Report>(&body).unwrap().score, 2);
+        // 报告带有签名，改动分数后无法通过校验
+        let mut report: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert!(key.verify(&report).is_ok());
+        report["score"] = serde_json::json!(3);
+        assert!(key.verify(&report).is_err());
+        assert_eq!(request(&addr, "PUT", &answers_path, &answers).await.0, 409);
+    }
+
+    #[test]
+    fn test_unique_questions() {
+        let bank: Bank = fixture::bank();
+        let single = bank[&QuestionType::SingleSelect][0].clone();
+        let judge = bank[&QuestionType::Judge][0].clone();
+        let paper = unique_questions(vec![
+            (QuestionType::SingleSelect, vec![single.clone(), single]),
+            (QuestionType::Judge, vec![judge]),
+        ]);
+        let counts = paper.iter().map(|(_, qs)| qs.len()).collect::<Vec<_>>();
+        assert_eq!(counts, vec![1, 1]);
+    }
+}
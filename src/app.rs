@@ -1,20 +1,34 @@
 use color_eyre::Result;
-use crossterm::event::KeyEvent;
+use color_eyre::eyre::eyre;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::prelude::Rect;
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 use tracing::{debug, info};
 
+use crate::action::ConfirmEvent;
 use crate::components::alert::Alert;
+use crate::components::audit::Audit;
+use crate::components::dashboard::Dashboard;
+use crate::components::examination::{self, ExaminationConfig};
+use crate::components::help::Help;
+use crate::components::remote::Remote;
+use crate::components::study::Study;
 use crate::components::timer::Timer;
 use crate::components::user_input::UserInput;
+use crate::server::Server;
+use crate::server::client::Connection;
+use crate::server::protocol::Paper;
 use crate::{
     action::Action,
     components::{Component, examination::Examination},
     config::Config,
     tui::{Event, Tui},
 };
+use crate::{i18n, theme};
+use ::examination::engine::{SuspendPolicy, blank};
+use chrono::{DateTime, Local};
 
 pub struct App {
     config: Config,
@@ -23,6 +37,8 @@ pub struct App {
     components: Vec<Box<dyn Component>>,
     should_quit: bool,
     should_suspend: bool,
+    /// 考试期间能否挂起
+    suspend: SuspendPolicy,
     mode: ModeHolderLock,
     last_tick_key_events: Vec<KeyEvent>,
     action_tx: mpsc::UnboundedSender<Action>,
@@ -35,6 +51,18 @@ pub enum Mode {
     Examination,
     Input,
     Alert,
+    /// 交卷前的答题汇总
+    Summary,
+    /// 输入题号跳转
+    Jump,
+    /// 快捷键帮助
+    Help,
+    /// 监考面板
+    Proctor,
+    /// 监考面板中输入广播消息
+    Broadcast,
+    /// 交卷后输入答案密钥的口令
+    Unlock,
 }
 
 #[derive(Default)]
@@ -62,42 +90,157 @@ impl ModeHolderLock {
 }
 
 impl App {
-    pub fn new(tick_rate: f64, frame_rate: f64) -> Result<Self> {
-        let (action_tx, action_rx) = mpsc::unbounded_channel();
+    /// 读取配置并初始化主题、界面语言等全局设置
+    pub fn load_config(accessible: bool, lang: Option<&str>) -> Result<Config> {
+        let mut config = Config::new()?;
+        config.config.accessible |= accessible;
+        theme::init(&config);
+        i18n::init(lang, config.config.language.as_deref());
+        blank::set_placeholder(config.config.blank_placeholder.clone());
+        Ok(config)
+    }
+
+    pub fn new(
+        config: Config,
+        tick_rate: f64,
+        frame_rate: f64,
+        practice: bool,
+        study: bool,
+    ) -> Result<Self> {
+        // 题库不含答案时无法即时判分
+        if (practice || study) && examination::answer_key_path(&config).exists() {
+            return Err(eyre!(i18n::t("unlock.unavailable")));
+        }
+        let (question_tx, question_rx) = mpsc::unbounded_channel();
+        let (answer_tx, answer_rx) = mpsc::unbounded_channel();
+        let mode_holder = ModeHolderLock(Arc::new(Mutex::new(ModeHolder::default())));
+        if study {
+            return Ok(Self::with_components(
+                config.clone(),
+                tick_rate,
+                frame_rate,
+                mode_holder.clone(),
+                vec![
+                    Box::new(Study::new(
+                        question_tx,
+                        answer_rx,
+                        mode_holder.clone(),
+                        config,
+                    )),
+                    Box::new(UserInput::new(question_rx, answer_tx, mode_holder.clone())),
+                    Box::new(Alert::new(mode_holder.clone())),
+                    Box::new(Help::new(mode_holder, true)),
+                ],
+            ));
+        }
+        let mut examination_config = Examination::load(config.clone());
+        if practice {
+            examination_config.set_practice(true);
+        }
+        // 按顺序进行组建渲染，后面的组件会覆盖前面的组件
+        let mut components: Vec<Box<dyn Component>> = vec![
+            Box::new(Examination::new(
+                question_tx,
+                answer_rx,
+                mode_holder.clone(),
+                config.clone(),
+                examination_config.clone(),
+            )),
+            Box::new(UserInput::new(question_rx, answer_tx, mode_holder.clone())),
+            Box::new(Alert::new(mode_holder.clone())),
+        ];
+        // 练习模式不计时，也不记录审计事件
+        if !examination_config.practice() {
+            components.push(Box::new(Timer::new(examination_config.duration())));
+            let path = config.config.data_dir.join("results").join(format!(
+                "result-{}.json",
+                Local::now().format("%Y%m%d-%H%M%S")
+            ));
+            components.push(Box::new(Audit::new(examination_config.name(), Some(path))));
+        }
+        // 帮助面板最后渲染，覆盖在其它组件之上
+        components.push(Box::new(Help::new(mode_holder.clone(), false)));
+        let mut app = Self::with_components(config, tick_rate, frame_rate, mode_holder, components);
+        if !examination_config.practice() {
+            app.suspend = examination_config.suspend();
+        }
+        Ok(app)
+    }
+
+    /// 联网考试：作答服务端发来的试卷，按服务端规定的截止时间计时
+    pub fn remote(
+        config: Config,
+        tick_rate: f64,
+        frame_rate: f64,
+        connection: Connection,
+        exam: ExaminationConfig,
+        paper: Paper,
+        deadline: DateTime<Local>,
+    ) -> Self {
         let (question_tx, question_rx) = mpsc::unbounded_channel();
         let (answer_tx, answer_rx) = mpsc::unbounded_channel();
         let mode_holder = ModeHolderLock(Arc::new(Mutex::new(ModeHolder::default())));
-        let config = Config::new()?;
-        let examination_config = Examination::load(config.clone());
-        Ok(Self {
+        let suspend = exam.suspend();
+        let components: Vec<Box<dyn Component>> = vec![
+            Box::new(Audit::new(exam.name(), None)),
+            Box::new(Examination::remote(
+                question_tx,
+                answer_rx,
+                mode_holder.clone(),
+                config.clone(),
+                exam,
+                paper,
+            )),
+            Box::new(UserInput::new(question_rx, answer_tx, mode_holder.clone())),
+            Box::new(Alert::new(mode_holder.clone())),
+            Box::new(Timer::until(deadline)),
+            Box::new(Remote::new(connection)),
+            Box::new(Help::new(mode_holder.clone(), false)),
+        ];
+        let mut app = Self::with_components(config, tick_rate, frame_rate, mode_holder, components);
+        app.suspend = suspend;
+        app
+    }
+
+    /// 监考面板：显示考试服务端中各考生的作答进度
+    pub fn proctor(config: Config, tick_rate: f64, frame_rate: f64, server: Arc<Server>) -> Self {
+        let mode_holder = ModeHolderLock(Arc::new(Mutex::new(ModeHolder::default())));
+        let components: Vec<Box<dyn Component>> = vec![
+            Box::new(Timer::until(server.deadline())),
+            Box::new(Dashboard::new(server, mode_holder.clone())),
+            Box::new(Alert::new(mode_holder.clone())),
+            Box::new(Help::new(mode_holder.clone(), false)),
+        ];
+        Self::with_components(config, tick_rate, frame_rate, mode_holder, components)
+    }
+
+    fn with_components(
+        config: Config,
+        tick_rate: f64,
+        frame_rate: f64,
+        mode: ModeHolderLock,
+        components: Vec<Box<dyn Component>>,
+    ) -> Self {
+        let (action_tx, action_rx) = mpsc::unbounded_channel();
+        Self {
             tick_rate,
             frame_rate,
-            // 按顺序进行组建渲染，后面的组件会覆盖前面的组件
-            components: vec![
-                Box::new(Examination::new(
-                    question_tx,
-                    answer_rx,
-                    mode_holder.clone(),
-                    config.clone(),
-                    examination_config.clone(),
-                )),
-                Box::new(UserInput::new(question_rx, answer_tx, mode_holder.clone())),
-                Box::new(Alert::new(mode_holder.clone())),
-                Box::new(Timer::new(examination_config.duration())),
-            ],
+            components,
             should_quit: false,
             should_suspend: false,
+            suspend: SuspendPolicy::Allow,
             config,
-            mode: mode_holder,
+            mode,
             last_tick_key_events: Vec::new(),
             action_tx,
             action_rx,
-        })
+        }
     }
 
     pub async fn run(&mut self) -> Result<()> {
         let mut tui = Tui::new()?
-            // .mouse(true) // uncomment this line to enable mouse support
+            .mouse(self.config.config.mouse)
+            .paste(true)
             .tick_rate(self.tick_rate)
             .frame_rate(self.frame_rate);
         tui.enter()?;
@@ -157,6 +300,7 @@ impl App {
         let Some(keymap) = self.config.keybindings.get(&self.mode.get_mode()) else {
             return Ok(());
         };
+        let key = normalize_key(key);
         match keymap.get(&vec![key]) {
             Some(action) => {
                 info!("Got action: {action:?}");
@@ -187,7 +331,8 @@ impl App {
                     self.last_tick_key_events.drain(..);
                 }
                 Action::Quit => self.should_quit = true,
-                Action::Suspend => self.should_suspend = true,
+                Action::Suspend => self.request_suspend()?,
+                Action::Confirm(ConfirmEvent::Suspend) => self.should_suspend = true,
                 Action::Resume => self.should_suspend = false,
                 Action::ClearScreen => tui.terminal.clear()?,
                 Action::Resize(w, h) => self.handle_resize(tui, w, h)?,
@@ -203,6 +348,23 @@ impl App {
         Ok(())
     }
 
+    /// 按考试配置决定直接挂起、提示后挂起或禁止挂起
+    fn request_suspend(&self) -> Result<()> {
+        let action = match self.suspend {
+            SuspendPolicy::Allow => Action::Confirm(ConfirmEvent::Suspend),
+            SuspendPolicy::Warn => Action::Alert(
+                i18n::t("audit.confirm_suspend").to_string(),
+                ConfirmEvent::Suspend,
+            ),
+            SuspendPolicy::Forbid => Action::Alert(
+                i18n::t("audit.suspend_forbidden").to_string(),
+                ConfirmEvent::Nothing,
+            ),
+        };
+        self.action_tx.send(action)?;
+        Ok(())
+    }
+
     fn handle_resize(&mut self, tui: &mut Tui, w: u16, h: u16) -> Result<()> {
         tui.resize(Rect::new(0, 0, w, h))?;
         self.render(tui)?;
@@ -222,3 +384,13 @@ impl App {
         Ok(())
     }
 }
+
+/// 部分终端输入 `:`、`?` 等符号时会附带 Shift 修饰键，与配置中的写法统一去掉
+pub fn normalize_key(key: KeyEvent) -> KeyEvent {
+    match key.code {
+        KeyCode::Char(c) if !c.is_ascii_alphabetic() => {
+            KeyEvent::new(key.code, key.modifiers - KeyModifiers::SHIFT)
+        }
+        _ => KeyEvent::new(key.code, key.modifiers),
+    }
+}
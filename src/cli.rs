@@ -1,4 +1,6 @@
-use clap::Parser;
+use chrono::NaiveTime;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 use crate::config::{get_config_dir, get_data_dir};
 
@@ -12,6 +14,92 @@ pub struct Cli {
     /// Frame rate, i.e. number of frames per second
     #[arg(short, long, value_name = "FLOAT", default_value_t = 60.0)]
     pub frame_rate: f64,
+
+    /// Practice mode: grade each answer immediately, without timer or final records
+    #[arg(short, long)]
+    pub practice: bool,
+
+    /// Study mode: review due questions from the bank with spaced repetition
+    #[arg(short, long, conflicts_with = "practice")]
+    pub study: bool,
+
+    /// Accessibility mode: mark right and wrong answers with text, disable blinking
+    #[arg(short, long)]
+    pub accessible: bool,
+
+    /// Interface language, e.g. zh-CN or en; defaults to the config file or LANG
+    #[arg(short, long, value_name = "LANG")]
+    pub lang: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Host the exam on the LAN for several candidates and collect their scores
+    Serve {
+        /// Address to listen on
+        #[arg(long, value_name = "ADDR", default_value = "0.0.0.0:7878")]
+        addr: String,
+
+        /// Draw a separate paper for every candidate instead of one shared paper
+        #[arg(long)]
+        shuffle: bool,
+
+        /// Shared start time today, e.g. 14:30; defaults to now
+        #[arg(long, value_name = "HH:MM", value_parser = parse_time)]
+        start: Option<NaiveTime>,
+
+        /// Print events to the console instead of showing the proctor dashboard
+        #[arg(long)]
+        headless: bool,
+
+        /// Password of the answer key, if the bank is sealed; prompted for when omitted
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Serve exams over an HTTP JSON API without the terminal UI
+    Api {
+        /// Address to listen on
+        #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:8080")]
+        addr: String,
+
+        /// Password of the answer key, if the bank is sealed; prompted for when omitted
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Move the answers of a question bank into a password-protected answer key
+    Seal {
+        /// Question bank with answers; the bank without answers and `answer.key` are written to the data directory
+        source: PathBuf,
+
+        /// Password of the answer key; prompted for when omitted
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Check that a signed result file or report has not been altered
+    Verify {
+        /// Result file saved by `serve` or report returned by `api`
+        file: PathBuf,
+
+        /// Signing key; defaults to `signing.key` in the config directory
+        #[arg(long, value_name = "PATH")]
+        key: Option<PathBuf>,
+    },
+    /// Take an exam hosted by `examination serve`
+    Join {
+        /// Server address, e.g. 192.168.1.10:7878
+        addr: String,
+
+        /// Candidate name shown to the proctor
+        #[arg(short, long)]
+        name: String,
+    },
+}
+
+fn parse_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value, "%H:%M").map_err(|e| e.to_string())
 }
 
 const VERSION_MESSAGE: &str = concat!(
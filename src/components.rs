@@ -1,8 +1,8 @@
 use color_eyre::Result;
 use crossterm::event::{KeyEvent, MouseEvent};
 use ratatui::{
-    layout::{Rect, Size},
     Frame,
+    layout::{Rect, Size},
 };
 use tokio::sync::mpsc::UnboundedSender;
 
@@ -10,8 +10,13 @@ use crate::{action::Action, config::Config, tui::Event};
 
 pub mod alert;
 mod area_util;
+pub mod audit;
+pub mod dashboard;
 pub mod examination;
 pub mod fps;
+pub mod help;
+pub mod remote;
+pub mod study;
 pub mod timer;
 pub mod user_input;
 
@@ -72,6 +77,7 @@ pub trait Component {
         let action = match event {
             Some(Event::Key(key_event)) => self.handle_key_event(key_event)?,
             Some(Event::Mouse(mouse_event)) => self.handle_mouse_event(mouse_event)?,
+            Some(Event::Paste(text)) => self.handle_paste_event(text)?,
             _ => None,
         };
         Ok(action)
@@ -102,6 +108,19 @@ pub trait Component {
         let _ = mouse; // to appease clippy
         Ok(None)
     }
+    /// Handle bracketed paste events and produce actions if necessary.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The pasted text.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<Action>>` - An action to be processed or none.
+    fn handle_paste_event(&mut self, text: String) -> Result<Option<Action>> {
+        let _ = text; // to appease clippy
+        Ok(None)
+    }
     /// Update the state of the component based on a received action. (REQUIRED)
     ///
     /// # Arguments
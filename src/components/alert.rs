@@ -2,13 +2,16 @@ use crate::action::{Action, ConfirmEvent};
 use crate::app::{Mode, ModeHolderLock};
 use crate::components::Component;
 use crate::components::area_util::centered_rect;
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::components::help;
+use crate::config::{Config, KeyBindings};
+use crate::theme;
 use ratatui::Frame;
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Layout, Rect};
-use ratatui::style::{Color, Style};
+use ratatui::style::Style;
 use ratatui::text::{Line, Text};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph, Widget};
+use std::collections::VecDeque;
 
 pub struct Alert {
     /// alert message
@@ -17,6 +20,12 @@ pub struct Alert {
     mode_holder: ModeHolderLock,
     /// 确认事件
     confirm_event: ConfirmEvent,
+    /// 快捷键配置，用于生成操作提示
+    keybindings: KeyBindings,
+    /// 打开提示框前的模式，全部提示关闭后恢复
+    previous: Mode,
+    /// 等待展示的提示，当前提示关闭后依次弹出
+    queue: VecDeque<(String, ConfirmEvent)>,
 }
 
 impl Widget for &mut Alert {
@@ -25,8 +34,14 @@ impl Widget for &mut Alert {
         Self: Sized,
     {
         match self.mode_holder.get_mode() {
-            Mode::Examination => {}
-            Mode::Input => {}
+            Mode::Examination
+            | Mode::Input
+            | Mode::Summary
+            | Mode::Jump
+            | Mode::Help
+            | Mode::Proctor
+            | Mode::Broadcast
+            | Mode::Unlock => {}
             Mode::Alert => {
                 let area = centered_rect(50, 100, area);
                 let [_, alert_area, _] = Layout::vertical([
@@ -40,14 +55,24 @@ impl Widget for &mut Alert {
                     Layout::vertical([Constraint::Length(1), Constraint::Length(3)])
                         .areas(alert_area);
                 let (msg, style) = (
-                    vec!["Esc to quit, Enter to submit.".into()],
+                    vec![
+                        help::footer(
+                            &self.keybindings,
+                            Mode::Alert,
+                            &[
+                                (Action::ConfirmDialog, "footer.submit"),
+                                (Action::CancelDialog, "footer.quit"),
+                            ],
+                        )
+                        .into(),
+                    ],
                     Style::default(),
                 );
                 let text = Text::from(Line::from(msg)).patch_style(style);
                 let help_message = Paragraph::new(text);
                 help_message.render(help_area, buf);
                 let msg = Paragraph::new(self.msg.as_str())
-                    .style(Style::default().fg(Color::Yellow))
+                    .style(theme::style("alert"))
                     .block(Block::default().borders(Borders::ALL));
                 msg.render(msg_area, buf);
             }
@@ -56,25 +81,37 @@ impl Widget for &mut Alert {
 }
 
 impl Component for Alert {
-    fn handle_key_event(&mut self, key: KeyEvent) -> color_eyre::Result<Option<Action>> {
-        match self.mode_holder.get_mode() {
-            Mode::Alert => match key.code {
-                KeyCode::Enter => Ok(Some(Action::Confirm(self.confirm_event.clone()))),
-                KeyCode::Esc => {
-                    self.close();
-                    Ok(None)
-                }
-                _ => Ok(None),
-            },
-            _ => Ok(None),
-        }
+    fn register_config_handler(&mut self, config: Config) -> color_eyre::Result<()> {
+        self.keybindings = config.keybindings;
+        Ok(())
     }
 
     fn update(&mut self, action: Action) -> color_eyre::Result<Option<Action>> {
-        if let Action::Alert(msg, confirm_event) = action {
-            self.msg = msg;
-            self.confirm_event = confirm_event;
-            self.mode_holder.set_mode(Mode::Alert);
+        match action {
+            // 已有提示在展示时排队，避免覆盖未确认的提示
+            Action::Alert(msg, confirm_event) if self.mode_holder.get_mode() == Mode::Alert => {
+                self.queue.push_back((msg, confirm_event));
+            }
+            Action::Alert(msg, confirm_event) => {
+                self.previous = self.mode_holder.get_mode();
+                self.msg = msg;
+                self.confirm_event = confirm_event;
+                self.mode_holder.set_mode(Mode::Alert);
+            }
+            // 仅作提示的消息确认后直接关闭
+            Action::ConfirmDialog
+                if self.mode_holder.get_mode() == Mode::Alert
+                    && self.confirm_event == ConfirmEvent::Nothing =>
+            {
+                self.close()
+            }
+            Action::ConfirmDialog if self.mode_holder.get_mode() == Mode::Alert => {
+                let confirm_event = self.confirm_event.clone();
+                self.close();
+                return Ok(Some(Action::Confirm(confirm_event)));
+            }
+            Action::CancelDialog if self.mode_holder.get_mode() == Mode::Alert => self.close(),
+            _ => {}
         }
         Ok(None)
     }
@@ -91,10 +128,60 @@ impl Alert {
             msg: String::new(),
             mode_holder,
             confirm_event: ConfirmEvent::Nothing,
+            keybindings: KeyBindings::default(),
+            previous: Mode::default(),
+            queue: VecDeque::new(),
         }
     }
 
+    /// 关闭当前提示：还有排队的提示时展示下一条，否则回到打开前的模式
     fn close(&mut self) {
-        self.mode_holder.set_mode(Mode::Examination);
+        match self.queue.pop_front() {
+            Some((msg, confirm_event)) => {
+                self.msg = msg;
+                self.confirm_event = confirm_event;
+            }
+            None => {
+                self.msg.clear();
+                self.confirm_event = ConfirmEvent::Nothing;
+                self.mode_holder.set_mode(self.previous);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::action::{Action, ConfirmEvent};
+    use crate::app::{Mode, ModeHolder, ModeHolderLock};
+    use crate::components::Component;
+    use crate::components::alert::Alert;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_restore_mode_and_queue() {
+        let mode_holder = ModeHolderLock(Arc::new(Mutex::new(ModeHolder::default())));
+        mode_holder.set_mode(Mode::Input);
+        let mut alert = Alert::new(mode_holder.clone());
+        alert
+            .update(Action::Alert("score".to_string(), ConfirmEvent::Score))
+            .unwrap();
+        alert
+            .update(Action::Alert("notice".to_string(), ConfirmEvent::Nothing))
+            .unwrap();
+        assert_eq!(mode_holder.get_mode(), Mode::Alert);
+        assert_eq!(alert.msg, "score");
+
+        // 确认第一条提示后展示排队的提示，不丢失确认事件
+        assert_eq!(
+            alert.update(Action::ConfirmDialog).unwrap(),
+            Some(Action::Confirm(ConfirmEvent::Score))
+        );
+        assert_eq!(mode_holder.get_mode(), Mode::Alert);
+        assert_eq!(alert.msg, "notice");
+
+        // 全部关闭后回到打开提示框前的模式
+        assert_eq!(alert.update(Action::ConfirmDialog).unwrap(), None);
+        assert_eq!(mode_holder.get_mode(), Mode::Input);
     }
 }
@@ -0,0 +1,160 @@
+//! 审计记录：收集切出窗口、挂起、调整终端大小、浏览与修改作答等事件。
+//!
+//! 本机考试在交卷、判分与退出时连同得分和试卷保存到数据目录的成绩文件，联网考试由 [`Remote`](crate::components::remote::Remote) 同步给服务端并附在成绩中。
+//!
+//! 本机的成绩文件不签名：考生能读到本机的任何密钥，签名无法防止考生修改后重新签名。需要可信成绩时使用联网考试，由服务端判分并签名。
+
+use crate::action::{Action, ConfirmEvent};
+use crate::components::Component;
+use crate::tui::Event;
+use chrono::{DateTime, Local};
+use color_eyre::Result;
+use examination::engine::{AuditEvent, AuditKind, AuditLog, Paper, QuestionTime};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use tracing::error;
+
+pub struct Audit {
+    log: AuditLog,
+    exam: String,
+    /// 本机考试保存成绩文件的路径，联网考试为 None
+    path: Option<PathBuf>,
+    /// 判分后的得分、交卷时间与带答案的试卷
+    result: Option<(u16, DateTime<Local>, Paper)>,
+}
+
+/// 本机考试的成绩文件，字段与服务端成绩文件中的记录一致；未判分就退出时得分与试卷为空
+#[derive(Serialize)]
+struct LocalResult<'a> {
+    exam: &'a str,
+    score: Option<u16>,
+    submitted_at: Option<DateTime<Local>>,
+    paper: Option<&'a Paper>,
+    time_spent: Vec<QuestionTime>,
+    audit: &'a AuditLog,
+}
+
+impl Audit {
+    pub fn new(exam: &str, path: Option<PathBuf>) -> Self {
+        Self {
+            log: AuditLog::default(),
+            exam: exam.to_string(),
+            path,
+            result: None,
+        }
+    }
+
+    fn record(&mut self, kind: AuditKind) -> Option<Action> {
+        let event = AuditEvent::now(kind);
+        self.log.push(event.clone());
+        Some(Action::Audited(event))
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = LocalResult {
+            exam: &self.exam,
+            score: self.result.as_ref().map(|(score, _, _)| *score),
+            submitted_at: self.result.as_ref().map(|(_, at, _)| *at),
+            paper: self.result.as_ref().map(|(_, _, paper)| paper),
+            time_spent: self.log.time_spent(),
+            audit: &self.log,
+        };
+        let saved = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .map_err(|e| e.to_string())
+            .and_then(|_| serde_json::to_string_pretty(&result).map_err(|e| e.to_string()))
+            .and_then(|json| fs::write(path, json).map_err(|e| e.to_string()));
+        if let Err(e) = saved {
+            error!("Failed to save result to {}: {e}", path.display());
+        }
+    }
+}
+
+impl Component for Audit {
+    fn handle_events(&mut self, event: Option<Event>) -> Result<Option<Action>> {
+        Ok(match event {
+            Some(Event::FocusLost) => self.record(AuditKind::FocusLost),
+            Some(Event::FocusGained) => self.record(AuditKind::FocusGained),
+            _ => None,
+        })
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        let kind = match action {
+            Action::Suspend => AuditKind::SuspendAttempt,
+            Action::Confirm(ConfirmEvent::Suspend) => AuditKind::Suspended,
+            Action::Resume => AuditKind::Resumed,
+            Action::Resize(width, height) => AuditKind::Resize { width, height },
+            Action::Viewing(question_type, index) => AuditKind::Viewed {
+                question_type,
+                index,
+            },
+            Action::AnswerChanged(question_type, index, user_input) => AuditKind::AnswerChanged {
+                question_type,
+                index,
+                user_input,
+            },
+            Action::Submitted(time_spent) => {
+                let action = self.record(AuditKind::Submitted { time_spent });
+                self.save();
+                return Ok(action);
+            }
+            // 判分后把得分与试卷写入成绩文件
+            Action::Graded(score, paper) => {
+                self.result = Some((score, Local::now(), paper));
+                self.save();
+                return Ok(None);
+            }
+            // 未交卷就退出时也保留记录
+            Action::Quit => {
+                self.save();
+                return Ok(None);
+            }
+            _ => return Ok(None),
+        };
+        Ok(self.record(kind))
+    }
+
+    fn draw(&mut self, _frame: &mut Frame, _area: Rect) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::action::Action;
+    use crate::components::Component;
+    use crate::components::audit::Audit;
+    use examination::engine::Paper;
+    use std::fs;
+
+    #[test]
+    fn test_local_result() {
+        let dir = std::env::temp_dir().join(format!("examination-audit-{}", std::process::id()));
+        let path = dir.join("result.json");
+        let mut audit = Audit::new("quiz", Some(path.clone()));
+        audit.update(Action::Submitted(vec![])).unwrap();
+        let paper: Paper = serde_json::from_str(
+            r#"[["Judge", [{"Judge": {"question": "1 + 1 = 2", "answer": "Yes", "user_input": "Yes", "score": 2}}]]]"#,
+        )
+        .unwrap();
+        audit.update(Action::Graded(2, paper)).unwrap();
+
+        let result: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        // 本机成绩文件不签名
+        assert!(result.get("signature").is_none());
+        assert_eq!(result["exam"], "quiz");
+        assert_eq!(result["score"], 2);
+        assert!(result["paper"].to_string().contains("1 + 1 = 2"));
+        assert!(result["audit"].to_string().contains("submitted"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,320 @@
+//! 监考面板：列出所有考生的作答进度与交卷情况，可延长考试时间、广播消息或强制收卷。
+
+use crate::action::{Action, ConfirmEvent};
+use crate::app::{Mode, ModeHolderLock};
+use crate::components::Component;
+use crate::components::examination::QuestionTypeView;
+use crate::components::help;
+use crate::config::Config;
+use crate::server::{CandidateStatus, Server};
+use crate::{i18n, theme};
+use chrono::TimeDelta;
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Frame;
+use ratatui::layout::Constraint::{Length, Min};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Row, Table};
+use std::sync::Arc;
+
+/// 每次延长的分钟数
+const EXTEND_MINUTES: i64 = 5;
+
+/// 剩余时间的显示文本，例如 `01:05:09`
+fn remaining_text(remaining: TimeDelta) -> String {
+    let secs = remaining.num_seconds();
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+pub struct Dashboard {
+    server: Arc<Server>,
+    mode_holder: ModeHolderLock,
+    config: Config,
+    /// 正在输入的广播消息
+    broadcast_input: String,
+}
+
+impl Dashboard {
+    pub fn new(server: Arc<Server>, mode_holder: ModeHolderLock) -> Self {
+        mode_holder.set_mode(Mode::Proctor);
+        Self {
+            server,
+            mode_holder,
+            config: Config::default(),
+            broadcast_input: String::new(),
+        }
+    }
+
+    fn confirm_broadcast(&mut self) {
+        let message = std::mem::take(&mut self.broadcast_input);
+        if !message.trim().is_empty() {
+            self.server.broadcast(message.trim());
+        }
+        self.mode_holder.set_mode(Mode::Proctor);
+    }
+
+    fn table(candidates: &[CandidateStatus]) -> Table<'static> {
+        let question_types = candidates
+            .first()
+            .map(|c| c.answered.iter().map(|(t, _, _)| *t).collect::<Vec<_>>())
+            .unwrap_or_default();
+        let mut header = vec![
+            Span::raw(i18n::t("proctor.name")),
+            Span::raw(i18n::t("proctor.status")),
+            Span::raw(i18n::t("proctor.focus_lost")),
+            Span::raw(i18n::t("proctor.remaining")),
+        ];
+        header.extend(
+            question_types
+                .iter()
+                .map(|t| Span::styled(t.label(), t.accent())),
+        );
+        let rows = candidates.iter().map(|c| {
+            let status = match (c.score, c.online) {
+                (Some(score), _) => Span::styled(
+                    i18n::tf("proctor.score", &[("score", &score)]),
+                    theme::style("question.right"),
+                ),
+                (None, true) => Span::raw(i18n::t("proctor.online")),
+                (None, false) => Span::styled(i18n::t("proctor.offline"), theme::style("error")),
+            };
+            // 切出过考试窗口的考生突出显示
+            let focus_lost = match c.focus_lost {
+                0 => Span::raw("0"),
+                n => Span::styled(n.to_string(), theme::style("error")),
+            };
+            let remaining = Span::raw(c.remaining.map(remaining_text).unwrap_or_default());
+            let mut cells = vec![Span::raw(c.name.clone()), status, focus_lost, remaining];
+            cells.extend(
+                c.answered
+                    .iter()
+                    .map(|(_, answered, total)| Span::raw(format!("{answered}/{total}"))),
+            );
+            Row::new(cells)
+        });
+        let mut widths = vec![
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ];
+        widths.extend(question_types.iter().map(|_| Constraint::Fill(1)));
+        Table::new(rows, widths)
+            .header(Row::new(header).style(theme::style("title")))
+            .block(Block::default().borders(Borders::ALL))
+    }
+
+    fn footer(&self) -> Line<'static> {
+        let line = match self.mode_holder.get_mode() {
+            Mode::Broadcast => i18n::tf(
+                "proctor.broadcast",
+                &[
+                    ("input", &self.broadcast_input),
+                    (
+                        "hint",
+                        &help::footer(
+                            &self.config.keybindings,
+                            Mode::Broadcast,
+                            &[
+                                (Action::ConfirmDialog, "footer.confirm"),
+                                (Action::CancelDialog, "footer.cancel"),
+                            ],
+                        ),
+                    ),
+                ],
+            ),
+            _ => help::footer(
+                &self.config.keybindings,
+                Mode::Proctor,
+                &[
+                    (Action::ExtendTime, "footer.extend"),
+                    (Action::StartBroadcast, "footer.broadcast"),
+                    (Action::SubmitAll, "footer.submit_all"),
+                    (Action::Help, "footer.help"),
+                    (Action::StopExam, "footer.quit"),
+                ],
+            ),
+        };
+        Line::raw(line).centered()
+    }
+}
+
+impl Component for Dashboard {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.mode_holder.get_mode() != Mode::Broadcast {
+            return Ok(None);
+        }
+        match key.code {
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.broadcast_input.push(c)
+            }
+            KeyCode::Backspace => {
+                self.broadcast_input.pop();
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match (self.mode_holder.get_mode(), action) {
+            (Mode::Proctor, Action::ExtendTime) => {
+                self.server.extend(EXTEND_MINUTES);
+                return Ok(Some(Action::Deadline(self.server.deadline())));
+            }
+            (Mode::Proctor, Action::StartBroadcast) => {
+                self.broadcast_input.clear();
+                self.mode_holder.set_mode(Mode::Broadcast);
+            }
+            (Mode::Proctor, Action::SubmitAll) => {
+                return Ok(Some(Action::Alert(
+                    i18n::t("proctor.confirm_submit_all").to_string(),
+                    ConfirmEvent::SubmitAll,
+                )));
+            }
+            (_, Action::Confirm(ConfirmEvent::SubmitAll)) => self.server.submit_all(),
+            // 考试服务随面板一起退出，仍有考生在线时先确认
+            (Mode::Proctor, Action::StopExam) => {
+                let online = self
+                    .server
+                    .snapshot()
+                    .iter()
+                    .filter(|c| c.online && c.score.is_none())
+                    .count();
+                if online > 0 {
+                    return Ok(Some(Action::Alert(
+                        i18n::tf("proctor.confirm_stop", &[("online", &online)]),
+                        ConfirmEvent::StopExam,
+                    )));
+                }
+                return Ok(Some(Action::Quit));
+            }
+            (_, Action::Confirm(ConfirmEvent::StopExam)) => return Ok(Some(Action::Quit)),
+            // 任何方式退出（包括其它模式下的 ctrl-c）都先收卷保存
+            (_, Action::Quit) => self.server.submit_all(),
+            (Mode::Broadcast, Action::ConfirmDialog) => self.confirm_broadcast(),
+            (Mode::Broadcast, Action::CancelDialog) => {
+                self.broadcast_input.clear();
+                self.mode_holder.set_mode(Mode::Proctor);
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let candidates = self.server.snapshot();
+        let [title_area, stats_area, table_area, events_area, footer_area] =
+            Layout::vertical([Length(1), Length(1), Min(0), Length(8), Length(1)]).areas(area);
+        frame.render_widget(
+            Span::styled(
+                i18n::tf("proctor.title", &[("name", &self.server.exam().name())]),
+                theme::style("title"),
+            ),
+            title_area,
+        );
+        let online = candidates.iter().filter(|c| c.online).count();
+        let submitted = candidates.iter().filter(|c| c.score.is_some()).count();
+        frame.render_widget(
+            Line::raw(i18n::tf(
+                "proctor.stats",
+                &[
+                    ("total", &candidates.len()),
+                    ("online", &online),
+                    ("submitted", &submitted),
+                ],
+            )),
+            stats_area,
+        );
+        frame.render_widget(Self::table(&candidates), table_area);
+        let events = self
+            .server
+            .recent_events(events_area.height.saturating_sub(2) as usize)
+            .into_iter()
+            .map(Line::raw)
+            .collect::<Vec<Line>>();
+        frame.render_widget(
+            Paragraph::new(events).block(
+                Block::default()
+                    .title(i18n::t("proctor.events"))
+                    .borders(Borders::ALL),
+            ),
+            events_area,
+        );
+        frame.render_widget(self.footer(), footer_area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::action::{Action, ConfirmEvent};
+    use crate::app::{ModeHolder, ModeHolderLock};
+    use crate::components::Component;
+    use crate::components::dashboard::{Dashboard, remaining_text};
+    use crate::fixture;
+    use crate::server::client::Connection;
+    use crate::server::protocol::ServerMessage;
+    use crate::server::{ServeOptions, Server};
+    use chrono::TimeDelta;
+    use std::fs;
+    use std::sync::{Arc, Mutex};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_remaining_text() {
+        assert_eq!(remaining_text(TimeDelta::seconds(3909)), "01:05:09");
+        assert_eq!(remaining_text(TimeDelta::zero()), "00:00:00");
+    }
+
+    #[tokio::test]
+    async fn test_stop_exam() {
+        let results_dir =
+            std::env::temp_dir().join(format!("examination-stop-{}", std::process::id()));
+        let options = ServeOptions {
+            results_dir: results_dir.clone(),
+            ..ServeOptions::default()
+        };
+        let server = Arc::new(Server::new(fixture::exam(), fixture::bank(), options));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(server.clone().run(listener));
+        let mode_holder = ModeHolderLock(Arc::new(Mutex::new(ModeHolder::default())));
+        let mut dashboard = Dashboard::new(server, mode_holder);
+
+        let mut bob = Connection::join(&addr, "bob", None).await.unwrap();
+        assert!(matches!(
+            bob.recv().await.unwrap(),
+            Some(ServerMessage::Token(_))
+        ));
+        assert!(matches!(
+            bob.recv().await.unwrap(),
+            Some(ServerMessage::Paper { .. })
+        ));
+        // 有考生在线时先确认，确认后退出
+        assert!(matches!(
+            dashboard.update(Action::StopExam).unwrap(),
+            Some(Action::Alert(_, ConfirmEvent::StopExam))
+        ));
+        assert_eq!(
+            dashboard
+                .update(Action::Confirm(ConfirmEvent::StopExam))
+                .unwrap(),
+            Some(Action::Quit)
+        );
+        // 退出前为未交卷的考生收卷并保存成绩
+        dashboard.update(Action::Quit).unwrap();
+        assert!(matches!(
+            bob.recv().await.unwrap(),
+            Some(ServerMessage::Result { .. })
+        ));
+        assert!(fs::read_dir(&results_dir).unwrap().next().is_some());
+        fs::remove_dir_all(results_dir).unwrap();
+    }
+}
@@ -1,27 +1,34 @@
+pub mod markup;
 mod question;
 
+pub use examination::engine::question::to_letter;
+pub use examination::engine::{ExaminationConfig, Paper, QuestionEnum, QuestionType};
+pub use question::{InputKindView, QuestionView, checkbox, input_error_text, option_lines, radio};
+
 use super::Component;
 use crate::action::ConfirmEvent;
 use crate::app::{Mode, ModeHolderLock};
-use crate::components::examination::question::{
-    FillIn, Judge, MultiSelect, Question, SingleSelect,
-};
+use crate::components::area_util::centered_rect;
+use crate::components::help;
+use crate::tui::Event;
 use crate::{action::Action, config::Config};
+use crate::{i18n, theme};
 use color_eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use examination::engine::{self, AnswerKey, Bank, KeyError, QuestionTime};
 use linked_hash_map::LinkedHashMap;
 use ratatui::Frame;
 use ratatui::buffer::Buffer;
-use ratatui::layout::Constraint::{Length, Min};
-use ratatui::layout::{Alignment, Layout, Rect};
-use ratatui::style::palette::tailwind;
-use ratatui::style::{Color, Style, Stylize};
-use ratatui::text::{Line, Text};
+use ratatui::layout::Constraint::{Length, Min, Percentage};
+use ratatui::layout::{Alignment, Layout, Position, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::*;
-use serde::{Deserialize, Serialize};
-use std::fs::File;
-use std::io::Read;
-use strum::{Display, EnumIter, FromRepr, IntoEnumIterator};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+use strum::IntoEnumIterator;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 
 pub struct Examination {
@@ -36,22 +43,41 @@ pub struct Examination {
     score: Option<u16>,
     state: State,
     selected_tab: QuestionType,
-}
-
-#[derive(Serialize, Deserialize, Clone)]
-pub struct ExaminationConfig {
-    name: String,
-    duration: u64,
-    single_select: usize,
-    multi_select: usize,
-    judge: usize,
-    fill_in: usize,
-}
-
-impl ExaminationConfig {
-    pub fn duration(&self) -> u64 {
-        self.duration
-    }
+    /// 交卷后是否显示解析面板
+    show_explanation: bool,
+    /// 标记为待检查的题目
+    flagged: HashSet<(QuestionType, usize)>,
+    /// 正在输入的跳转题号，为 None 时表示未处于跳转输入状态
+    jump_input: String,
+    /// 交卷汇总中选中的条目
+    summary_state: ListState,
+    /// 纯文本视图及其滚动位置
+    linear: bool,
+    linear_scroll: u16,
+    /// 上次渲染时各标签的区域
+    tab_areas: Vec<(Rect, QuestionType)>,
+    /// 上次渲染时各题目的区域
+    item_areas: Vec<(Rect, usize)>,
+    /// 上次点击的时间与题目，用于识别双击
+    last_click: Option<(Instant, QuestionType, usize)>,
+    /// 是否为联网考试
+    remote: bool,
+    /// 题库不含答案，交卷后由监考老师输入口令解锁答案密钥再判分
+    sealed: bool,
+    /// 正在输入的口令
+    password_input: String,
+    /// 上次解锁失败的原因
+    unlock_error: Option<String>,
+    /// 正在浏览的题目，变化时记入审计记录
+    viewing: Option<(QuestionType, usize)>,
+    /// 每道题的累计用时
+    time_spent: HashMap<(QuestionType, usize), Duration>,
+    /// 上次累计用时的时刻
+    last_tick: Instant,
+    /// 终端是否处于焦点，失去焦点期间不计时
+    focused: bool,
+    /// 超过作答时限的题目，不能再修改
+    timed_out: HashSet<(QuestionType, usize)>,
 }
 
 #[derive(Eq, PartialEq, Copy, Clone)]
@@ -60,87 +86,189 @@ pub enum State {
     End,
 }
 
-#[derive(Eq, PartialEq, Copy, Clone, Deserialize, Serialize, Hash, Display, FromRepr, EnumIter)]
-pub enum QuestionType {
-    #[strum(to_string = "单选题")]
-    SingleSelect,
-    #[strum(to_string = "多选题")]
-    MultiSelect,
-    #[strum(to_string = "判断题")]
-    Judge,
-    #[strum(to_string = "填空题")]
-    FillIn,
-}
-
-struct QuestionTabInner {
+struct QuestionTabInner<'a> {
     questions: Vec<QuestionEnum>,
     state: State,
-    list_state: ListState,
+    list_state: &'a mut ListState,
+    /// 渲染后各题目在屏幕上的区域，用于鼠标点击定位
+    item_areas: &'a mut Vec<(Rect, usize)>,
+    /// 练习模式下已判分的题目，按交卷后的样式展示
+    graded: Vec<bool>,
+    /// 标记为待检查的题目
+    flagged: Vec<bool>,
+    /// 本页第一题在整张试卷中的序号，题号全卷连续
+    offset: usize,
+    /// 附在题目首行末尾的用时或超时提示
+    notes: Vec<Option<Span<'static>>>,
 }
 
-impl Widget for QuestionTabInner {
+impl Widget for QuestionTabInner<'_> {
     fn render(self, area: Rect, buf: &mut Buffer)
     where
         Self: Sized,
     {
+        // 预留高亮符号的宽度后按可用宽度折行
+        let width = area.width.saturating_sub(2) as usize;
         let texts = self
             .questions
             .iter()
             .enumerate()
-            .map(|(i, q)| q.convert_text(self.state, i))
+            .map(|(i, q)| {
+                let state = if self.graded.get(i).copied().unwrap_or_default() {
+                    State::End
+                } else {
+                    self.state
+                };
+                let mut text = q.convert_text(state, self.offset + i);
+                if self.flagged.get(i).copied().unwrap_or_default()
+                    && let Some(line) = text.lines.first_mut()
+                {
+                    line.spans.insert(0, Span::styled("⚑ ", *FLAG_STYLE));
+                }
+                if let Some(Some(note)) = self.notes.get(i)
+                    && let Some(line) = text.lines.first_mut()
+                {
+                    line.spans.push(Span::raw("  "));
+                    line.spans.push(note.clone());
+                }
+                markup::wrap(text, width)
+            })
             .collect::<Vec<Text>>();
+        let heights = texts.iter().map(Text::height).collect::<Vec<usize>>();
 
         let list = match self.state {
             State::Ing => List::from_iter(texts)
-                .style(Color::Gray)
-                .highlight_style(Style::default().fg(Color::LightBlue))
+                .style(theme::style("list"))
+                .highlight_style(theme::style("highlight"))
+                .highlight_symbol("> ")
+                .scroll_padding(1),
+            State::End => List::from_iter(texts)
+                .style(theme::style("list"))
                 .highlight_symbol("> ")
                 .scroll_padding(1),
-            State::End => List::from_iter(texts).style(Color::Gray).scroll_padding(1),
         };
-        let mut state = self.list_state.clone();
-        StatefulWidget::render(list, area, buf, &mut state)
+        StatefulWidget::render(list, area, buf, self.list_state);
+
+        self.item_areas.clear();
+        let mut y = area.y;
+        for (i, height) in heights.iter().enumerate().skip(self.list_state.offset()) {
+            if y >= area.bottom() {
+                break;
+            }
+            let height = (*height as u16).min(area.bottom() - y);
+            self.item_areas
+                .push((Rect::new(area.x, y, area.width, height), i));
+            y += height;
+        }
     }
 }
 
-impl QuestionType {
-    /// Get the previous tab, if there is no previous tab return the current tab.
-    pub(crate) fn previous(self) -> Self {
-        let current_index: usize = self as usize;
-        let previous_index = current_index.saturating_sub(1);
-        Self::from_repr(previous_index).unwrap_or(self)
-    }
+const DOUBLE_CLICK: Duration = Duration::from_millis(500);
+
+static FLAG_STYLE: LazyLock<Style, fn() -> Style> = LazyLock::new(|| theme::style("flag"));
+
+/// 答题卡：按题型列出全卷题号，用颜色区分已答、未答与标记状态
+struct Navigator<'a> {
+    questions: &'a LinkedHashMap<QuestionType, Vec<QuestionEnum>>,
+    flagged: &'a HashSet<(QuestionType, usize)>,
+    selected: Option<(QuestionType, usize)>,
+}
 
-    /// Get the next tab, if there is no next tab return the current tab.
-    pub(crate) fn next(self) -> Self {
-        let current_index = self as usize;
-        let next_index = current_index.saturating_add(1);
-        Self::from_repr(next_index).unwrap_or(self)
+impl Widget for Navigator<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let block = Block::default()
+            .title(i18n::t("navigator.title"))
+            .borders(Borders::LEFT);
+        let inner = block.inner(area);
+        block.render(area, buf);
+        // 每个题号占 4 列
+        let per_row = (inner.width as usize / 4).max(1);
+        let mut lines = vec![];
+        let mut number = 0;
+        for (question_type, questions) in self.questions.iter() {
+            if questions.is_empty() {
+                continue;
+            }
+            lines.push(Line::styled(question_type.label(), question_type.accent()));
+            for chunk in questions
+                .iter()
+                .enumerate()
+                .collect::<Vec<_>>()
+                .chunks(per_row)
+            {
+                let spans = chunk
+                    .iter()
+                    .map(|(i, q)| {
+                        number += 1;
+                        let mut style = if self.flagged.contains(&(*question_type, *i)) {
+                            theme::style("navigator.flagged")
+                        } else if q.answered() {
+                            theme::style("navigator.answered")
+                        } else {
+                            theme::style("navigator.unanswered")
+                        };
+                        if self.selected == Some((*question_type, *i)) {
+                            style = style.add_modifier(Modifier::REVERSED);
+                        }
+                        Span::styled(format!("{number:>3}"), style)
+                    })
+                    .flat_map(|span| [span, Span::raw(" ")])
+                    .collect::<Vec<Span>>();
+                lines.push(Line::from(spans));
+            }
+        }
+        lines.push(Line::default());
+        lines.push(Line::from(vec![
+            Span::styled("   ", theme::style("navigator.answered")),
+            Span::raw(i18n::t("navigator.answered")),
+            Span::styled("   ", theme::style("navigator.flagged")),
+            Span::raw(i18n::t("navigator.flagged")),
+        ]));
+        Paragraph::new(lines).render(inner, buf);
     }
+}
+
+/// 题型在终端中的名称与样式
+pub trait QuestionTypeView {
+    /// 当前语言下的题型名称
+    fn label(self) -> &'static str;
 
     /// Return tab's name as a styled `Line`
-    pub(crate) fn title(self) -> Line<'static> {
-        format!("  {self}  ")
-            .fg(tailwind::SLATE.c200)
-            .bg(self.palette().c900)
-            .into()
+    fn title(self) -> Line<'static>;
+
+    /// 题型的强调色，用于答题卡、交卷汇总与解析边框
+    fn accent(self) -> Style;
+}
+
+impl QuestionTypeView for QuestionType {
+    fn label(self) -> &'static str {
+        i18n::t(&format!("type.{}", self.key()))
     }
 
-    pub(crate) const fn palette(self) -> tailwind::Palette {
-        match self {
-            Self::SingleSelect => tailwind::BLUE,
-            Self::MultiSelect => tailwind::EMERALD,
-            Self::Judge => tailwind::INDIGO,
-            Self::FillIn => tailwind::RED,
-        }
+    fn title(self) -> Line<'static> {
+        Line::styled(
+            format!("  {}  ", self.label()),
+            theme::style(&format!("tab.{}", self.key())),
+        )
+    }
+
+    fn accent(self) -> Style {
+        theme::style(&format!("type.{}", self.key()))
     }
 }
-#[derive(Serialize, Deserialize, Clone)]
-pub enum QuestionEnum {
-    SingleSelect(SingleSelect),
-    MultiSelect(MultiSelect),
-    Judge(Judge),
-    FillIn(FillIn),
+
+/// 读取数据目录下的题库
+pub(crate) fn load_bank(config: &Config) -> Bank {
+    engine::load_bank(&config.config.data_dir.join("question.json"))
+        .expect("Fail to load question!")
+}
+
+/// 数据目录下的答案密钥，存在时题库中不含答案
+pub(crate) fn answer_key_path(config: &Config) -> PathBuf {
+    config.config.data_dir.join("answer.key")
 }
 
 impl Examination {
@@ -151,7 +279,44 @@ impl Examination {
         config: Config,
         ec: ExaminationConfig,
     ) -> Self {
-        let type_2_questions = QuestionEnum::load(config.clone(), ec.clone());
+        let type_2_questions = QuestionEnum::choose(&load_bank(&config), &ec);
+        let sealed = answer_key_path(&config).exists();
+        let mut examination = Self::with_questions(
+            question_tx,
+            answer_rx,
+            state_holder,
+            config,
+            ec,
+            type_2_questions,
+        );
+        examination.sealed = sealed;
+        examination
+    }
+
+    /// 联网考试：作答服务端发来的试卷，交卷时由服务端判分
+    pub fn remote(
+        question_tx: UnboundedSender<QuestionEnum>,
+        answer_rx: UnboundedReceiver<QuestionEnum>,
+        state_holder: ModeHolderLock,
+        config: Config,
+        ec: ExaminationConfig,
+        paper: Paper,
+    ) -> Self {
+        let questions = paper.into_iter().collect();
+        let mut examination =
+            Self::with_questions(question_tx, answer_rx, state_holder, config, ec, questions);
+        examination.remote = true;
+        examination
+    }
+
+    fn with_questions(
+        question_tx: UnboundedSender<QuestionEnum>,
+        answer_rx: UnboundedReceiver<QuestionEnum>,
+        state_holder: ModeHolderLock,
+        config: Config,
+        ec: ExaminationConfig,
+        type_2_questions: LinkedHashMap<QuestionType, Vec<QuestionEnum>>,
+    ) -> Self {
         let question_type = type_2_questions
             .iter()
             .next()
@@ -169,18 +334,44 @@ impl Examination {
             score: None,
             state: State::Ing,
             selected_tab: question_type,
+            show_explanation: false,
+            flagged: HashSet::new(),
+            jump_input: String::new(),
+            summary_state: ListState::default(),
+            linear: false,
+            linear_scroll: 0,
+            tab_areas: vec![],
+            item_areas: vec![],
+            last_click: None,
+            remote: false,
+            sealed: false,
+            password_input: String::new(),
+            unlock_error: None,
+            viewing: None,
+            time_spent: HashMap::new(),
+            last_tick: Instant::now(),
+            focused: true,
+            timed_out: HashSet::new(),
         };
         examination.list_state.select_first();
         examination
     }
 
     pub(crate) fn load(config: Config) -> ExaminationConfig {
-        let mut ec = String::new();
-        File::open(config.config.data_dir.join("examination.json"))
-            .unwrap()
-            .read_to_string(&mut ec)
-            .expect("Fail to load question!");
-        serde_json::from_slice::<ExaminationConfig>(ec.as_ref()).unwrap()
+        ExaminationConfig::load(&config.config.data_dir.join("examination.json"))
+            .expect("Fail to load examination!")
+    }
+
+    /// 练习模式下，所有输入框都已作答的题目即视为已判分
+    fn graded(&self, q: &QuestionEnum) -> bool {
+        self.examination_config.practice() && q.answered()
+    }
+
+    fn selected_graded(&self) -> bool {
+        self.list_state
+            .selected()
+            .and_then(|idx| self.current_questions().get(idx).cloned())
+            .is_some_and(|q| self.graded(&q))
     }
 
     pub fn current_questions(&self) -> Vec<QuestionEnum> {
@@ -193,28 +384,393 @@ impl Examination {
     fn cal_score(&self) -> u16 {
         self.questions
             .values()
-            .map(|qs| {
-                qs.iter()
-                    .map(|q| match q {
-                        QuestionEnum::SingleSelect(q) => q.cal_score(),
-                        QuestionEnum::MultiSelect(q) => q.cal_score(),
-                        QuestionEnum::Judge(q) => q.cal_score(),
-                        QuestionEnum::FillIn(q) => q.cal_score(),
-                    })
-                    .sum::<u16>()
-            })
+            .map(|qs| qs.iter().map(QuestionEnum::cal_score).sum::<u16>())
             .sum()
     }
 
     fn handle_submit(&mut self) -> Result<Option<Action>> {
         self.mode_holder.set_mode(Mode::Examination);
+        if let Some(tx) = &self.command_tx {
+            let _ = tx.send(Action::Submitted(self.question_times()));
+        }
+        // 联网考试的试卷不含答案，由服务端判分
+        if self.remote {
+            return Ok(Some(Action::RemoteSubmit));
+        }
+        // 题库不含答案，等待监考老师解锁答案密钥
+        if self.sealed {
+            self.password_input.clear();
+            self.unlock_error = None;
+            self.mode_holder.set_mode(Mode::Unlock);
+            return Ok(None);
+        }
         // 计算得分
         let score = self.cal_score();
-        self.score = Some(score);
-        Ok(Some(Action::Alert(
-            format!("您的最终得分是{score}"),
-            ConfirmEvent::Score,
-        )))
+        if self.examination_config.practice() {
+            self.score = Some(score);
+            let msg = i18n::tf("exam.practice_score", &[("score", &score)]);
+            return Ok(Some(Action::Alert(msg, ConfirmEvent::Score)));
+        }
+        // 与解锁答案后一样经由 Graded 展示得分，审计记录据此保存成绩文件
+        Ok(Some(Action::Graded(score, self.paper())))
+    }
+
+    /// 当前试卷，按题型顺序排列
+    fn paper(&self) -> Paper {
+        self.questions
+            .iter()
+            .map(|(t, qs)| (*t, qs.clone()))
+            .collect()
+    }
+
+    /// 当前题型第一题在整张试卷中的序号
+    fn offset(&self, question_type: QuestionType) -> usize {
+        self.questions
+            .iter()
+            .take_while(|(t, _)| **t != question_type)
+            .map(|(_, qs)| qs.len())
+            .sum()
+    }
+
+    fn toggle_flag(&mut self) {
+        if let Some(idx) = self.list_state.selected() {
+            let key = (self.selected_tab, idx);
+            if !self.flagged.remove(&key) {
+                self.flagged.insert(key);
+            }
+        }
+    }
+
+    /// 按全卷题号（从 1 开始）跳转到对应题型和题目
+    fn jump_to(&mut self, number: usize) {
+        let mut rest = number.saturating_sub(1);
+        for (question_type, questions) in self.questions.iter() {
+            if rest < questions.len() {
+                self.selected_tab = *question_type;
+                self.list_state.select(Some(rest));
+                return;
+            }
+            rest -= questions.len();
+        }
+    }
+
+    /// 弹框请用户输入答案
+    fn open_answer(&mut self) -> Result<()> {
+        if self.state != State::Ing
+            || (self.examination_config.lock_answer() && self.selected_graded())
+            || self.selected_timed_out()
+        {
+            return Ok(());
+        }
+        if let Some(q) = self
+            .list_state
+            .selected()
+            .and_then(|idx| self.current_questions().get(idx).cloned())
+        {
+            self.question_tx.send(q)?;
+        }
+        Ok(())
+    }
+
+    /// 在题目列表中直接选择选项，不支持的题型或越界的选项被忽略
+    fn select_option(&mut self, idx: usize) {
+        let Some(selected) = self.list_state.selected() else {
+            return;
+        };
+        let practice = self.examination_config.practice();
+        let locked = (self.examination_config.lock_answer() && self.selected_graded())
+            || self.selected_timed_out();
+        let Some(q) = self
+            .questions
+            .get_mut(&self.selected_tab)
+            .and_then(|qs| qs.get_mut(selected))
+        else {
+            return;
+        };
+        // 练习模式下作答即判分，多选题需在弹框中选好后一并提交
+        if locked || (practice && matches!(q, QuestionEnum::MultiSelect(_))) {
+            return;
+        }
+        q.toggle_option(idx);
+        self.answer_changed(self.selected_tab, selected);
+    }
+
+    fn selected_timed_out(&self) -> bool {
+        self.list_state
+            .selected()
+            .is_some_and(|idx| self.timed_out.contains(&(self.selected_tab, idx)))
+    }
+
+    /// 累计选中题目的用时：作答期间选中题目或打开其作答弹框时计时，超过时限后跳到下一题
+    fn track_time(&mut self, action: &Action) {
+        let now = Instant::now();
+        let elapsed = now - std::mem::replace(&mut self.last_tick, now);
+        // 挂起期间没有 Tick，恢复后不计入这段时间
+        if *action == Action::Resume
+            || !self.focused
+            || self.linear
+            || self.state != State::Ing
+            || !matches!(self.mode_holder.get_mode(), Mode::Examination | Mode::Input)
+        {
+            return;
+        }
+        let Some(idx) = self.list_state.selected() else {
+            return;
+        };
+        let key = (self.selected_tab, idx);
+        let spent = self.time_spent.entry(key).or_default();
+        *spent += elapsed;
+        if let Some(limit) = self.examination_config.question_time_limit()
+            && *spent >= limit
+            && self.timed_out.insert(key)
+        {
+            self.time_out(idx);
+        }
+    }
+
+    /// 当前题目超时：关闭作答弹框，跳到全卷的下一题
+    fn time_out(&mut self, idx: usize) {
+        // 先保存已确认的作答，以免记到下一题上
+        self.receive_answer();
+        // 弹框中未确认的输入不再保存，材料题已确认的子题也一并放弃
+        if self.mode_holder.get_mode() == Mode::Input {
+            self.mode_holder.set_mode(Mode::Examination);
+            if let Some(tx) = &self.command_tx {
+                let _ = tx.send(Action::DiscardInput);
+            }
+        }
+        self.jump_to(self.offset(self.selected_tab) + idx + 2);
+    }
+
+    /// 每道题的用时，按试卷顺序排列，未浏览过的题目不列出
+    fn question_times(&self) -> Vec<QuestionTime> {
+        self.questions
+            .iter()
+            .flat_map(|(question_type, qs)| (0..qs.len()).map(move |i| (*question_type, i)))
+            .filter_map(|(question_type, index)| {
+                self.time_spent
+                    .get(&(question_type, index))
+                    .map(|spent| QuestionTime {
+                        question_type,
+                        index,
+                        seconds: spent.as_secs() as i64,
+                    })
+            })
+            .collect()
+    }
+
+    /// 题目首行末尾的提示：交卷后显示用时，作答期间只提示已超时
+    fn time_note(&self, question_type: QuestionType, idx: usize) -> Option<Span<'static>> {
+        let key = (question_type, idx);
+        let timed_out = self.timed_out.contains(&key);
+        let note = match self.state {
+            State::Ing if timed_out => i18n::t("timer.timed_out").to_string(),
+            State::Ing => return None,
+            State::End => {
+                let spent = self.time_spent.get(&key)?.as_secs();
+                let time = format!("{}:{:02}", spent / 60, spent % 60);
+                let note = i18n::tf("timer.spent", &[("time", &time)]);
+                if timed_out {
+                    format!("{note} {}", i18n::t("timer.timed_out"))
+                } else {
+                    note
+                }
+            }
+        };
+        Some(Span::styled(note, theme::style("timer")))
+    }
+
+    /// 作答期间选中的题目变化时通知审计记录
+    fn notify_viewing(&mut self) {
+        if self.state != State::Ing {
+            return;
+        }
+        let viewing = self.list_state.selected().map(|i| (self.selected_tab, i));
+        if viewing == self.viewing {
+            return;
+        }
+        self.viewing = viewing;
+        if let (Some((question_type, idx)), Some(tx)) = (viewing, &self.command_tx) {
+            let _ = tx.send(Action::Viewing(question_type, idx));
+        }
+    }
+
+    /// 通知其他组件作答内容的变化，联网考试中据此把答案同步给服务端
+    fn answer_changed(&self, question_type: QuestionType, idx: usize) {
+        let Some(q) = self
+            .questions
+            .get(&question_type)
+            .and_then(|qs| qs.get(idx))
+        else {
+            return;
+        };
+        if let Some(tx) = &self.command_tx {
+            let _ = tx.send(Action::AnswerChanged(question_type, idx, q.user_input()));
+        }
+    }
+
+    fn handle_jump_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(c) if c.is_ascii_digit() => self.jump_input.push(c),
+            KeyCode::Backspace => {
+                self.jump_input.pop();
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_password_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.password_input.push(c)
+            }
+            KeyCode::Backspace => {
+                self.password_input.pop();
+            }
+            _ => {}
+        }
+    }
+
+    /// 用口令解锁答案密钥，换回答案后判分
+    fn unlock(&mut self) -> Option<Action> {
+        let password = std::mem::take(&mut self.password_input);
+        match AnswerKey::load(&answer_key_path(&self.config), &password) {
+            Ok(key) => {
+                let paper = key.restore_paper(&self.paper());
+                Some(Action::Graded(engine::paper::score(&paper), paper))
+            }
+            Err(KeyError::Locked) => {
+                self.unlock_error = Some(i18n::t("unlock.wrong_password").to_string());
+                None
+            }
+            Err(e) => {
+                self.unlock_error = Some(e.to_string());
+                None
+            }
+        }
+    }
+
+    fn confirm_jump(&mut self) {
+        if let Ok(number) = self.jump_input.parse::<usize>() {
+            self.jump_to(number);
+        }
+        self.mode_holder.set_mode(Mode::Examination);
+    }
+
+    /// 未作答或已标记的题目，按试卷顺序排列：(题型, 题型内序号, 全卷题号, 是否已答, 是否标记)
+    fn summary_entries(&self) -> Vec<(QuestionType, usize, usize, bool, bool)> {
+        let mut number = 0;
+        let mut entries = vec![];
+        for (question_type, questions) in self.questions.iter() {
+            for (i, q) in questions.iter().enumerate() {
+                number += 1;
+                let answered = q.answered();
+                let flagged = self.flagged.contains(&(*question_type, i));
+                if !answered || flagged {
+                    entries.push((*question_type, i, number, answered, flagged));
+                }
+            }
+        }
+        entries
+    }
+
+    /// 从交卷汇总跳转到选中的题目
+    fn jump_to_summary_entry(&mut self) {
+        let entries = self.summary_entries();
+        if let Some((_, _, number, _, _)) = self
+            .summary_state
+            .selected()
+            .and_then(|idx| entries.get(idx.min(entries.len().saturating_sub(1))))
+        {
+            self.jump_to(*number);
+            self.mode_holder.set_mode(Mode::Examination);
+        }
+    }
+
+    fn render_summary(&mut self, area: Rect, buf: &mut Buffer) {
+        let area = centered_rect(60, 70, area);
+        Clear.render(area, buf);
+        let block = Block::default()
+            .title(i18n::t("summary.title"))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .style(theme::style("popup"));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let [stat_area, list_area, help_area] =
+            Layout::vertical([Length(2), Min(0), Length(1)]).areas(inner);
+
+        let total = self.questions.values().map(Vec::len).sum::<usize>();
+        let answered = self
+            .questions
+            .values()
+            .flatten()
+            .filter(|q| q.answered())
+            .count();
+        let entries = self.summary_entries();
+        Paragraph::new(i18n::tf(
+            "summary.stats",
+            &[
+                ("total", &total),
+                ("answered", &answered),
+                ("unanswered", &(total - answered)),
+                ("flagged", &self.flagged.len()),
+            ],
+        ))
+        .alignment(Alignment::Center)
+        .render(stat_area, buf);
+
+        if entries.is_empty() {
+            Paragraph::new(i18n::t("summary.all_answered"))
+                .alignment(Alignment::Center)
+                .render(list_area, buf);
+        } else {
+            let items = entries
+                .iter()
+                .map(|(question_type, _, number, answered, flagged)| {
+                    let mut spans = vec![
+                        Span::styled(
+                            format!("{} ", question_type.label()),
+                            question_type.accent(),
+                        ),
+                        Span::raw(i18n::tf("summary.number", &[("number", number)])),
+                    ];
+                    if !answered {
+                        spans.push(Span::styled(
+                            i18n::t("summary.unanswered"),
+                            theme::style("error"),
+                        ));
+                    }
+                    if *flagged {
+                        spans.push(Span::styled(i18n::t("summary.flagged"), *FLAG_STYLE));
+                    }
+                    ListItem::new(Line::from(spans))
+                })
+                .collect::<Vec<ListItem>>();
+            if self.summary_state.selected().is_none() {
+                self.summary_state.select_first();
+            }
+            StatefulWidget::render(
+                List::new(items)
+                    .highlight_style(theme::style("highlight"))
+                    .highlight_symbol("> "),
+                list_area,
+                buf,
+                &mut self.summary_state,
+            );
+        }
+        Line::raw(help::footer(
+            &self.config.keybindings,
+            Mode::Summary,
+            &[
+                (Action::NextQuestion, "footer.select"),
+                (Action::JumpToSelected, "footer.jump"),
+                (Action::ConfirmDialog, "footer.submit"),
+                (Action::CancelDialog, "footer.cancel"),
+            ],
+        ))
+        .centered()
+        .render(help_area, buf);
     }
 
     pub fn next_tab(&mut self) {
@@ -227,10 +783,19 @@ impl Examination {
         self.selected_tab = self.selected_tab.previous();
     }
 
-    fn render_tabs(&self, area: Rect, buf: &mut Buffer) {
+    fn render_tabs(&mut self, area: Rect, buf: &mut Buffer) {
         // let titles = self.questions.keys().map(QuestionType::title);
         let titles = QuestionType::iter().map(QuestionType::title);
-        let highlight_style = (Color::default(), self.selected_tab.palette().c700);
+        // 记录每个标签的位置，用于鼠标点击切换，标签之间有一个字符的分隔符
+        self.tab_areas.clear();
+        let mut x = area.x;
+        for (question_type, title) in QuestionType::iter().zip(titles.clone()) {
+            let width = (title.width() as u16).min(area.right().saturating_sub(x));
+            self.tab_areas
+                .push((Rect::new(x, area.y, width, area.height), question_type));
+            x = x.saturating_add(width + 1);
+        }
+        let highlight_style = theme::style(&format!("tab.{}.selected", self.selected_tab.key()));
         let selected_tab_index = self.selected_tab as usize;
         Tabs::new(titles)
             .highlight_style(highlight_style)
@@ -252,48 +817,155 @@ impl Component for Examination {
         Ok(())
     }
 
+    fn handle_events(&mut self, event: Option<Event>) -> Result<Option<Action>> {
+        match event {
+            Some(Event::Key(key_event)) => self.handle_key_event(key_event),
+            Some(Event::Mouse(mouse_event)) => self.handle_mouse_event(mouse_event),
+            // 切出窗口期间不计入题目用时
+            Some(Event::FocusLost) => {
+                self.track_time(&Action::Tick);
+                self.focused = false;
+                Ok(None)
+            }
+            Some(Event::FocusGained) => {
+                self.last_tick = Instant::now();
+                self.focused = true;
+                Ok(None)
+            }
+            _ => Ok(None),
+        }
+    }
+
     fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
-        if self.mode_holder.get_mode() == Mode::Examination {
-            match key.code {
-                KeyCode::Down => self.list_state.select_next(),
-                KeyCode::Up => self.list_state.select_previous(),
-                KeyCode::Enter if self.state == State::Ing => {
-                    // 弹框请用户输入答案
-                    let idx = self.list_state.selected().unwrap();
-                    let current_questions = self.current_questions();
-                    let q = current_questions.get(idx).unwrap();
-                    self.question_tx.send(q.clone())?;
+        match self.mode_holder.get_mode() {
+            Mode::Jump => self.handle_jump_key(key),
+            Mode::Unlock => self.handle_password_key(key),
+            // 选项快捷键不可配置，但已绑定了动作的按键（如 vim 预设中的 h）优先作为动作处理
+            Mode::Examination => match key.code {
+                KeyCode::Char(c)
+                    if self.state == State::Ing
+                        && !key.modifiers.contains(KeyModifiers::CONTROL)
+                        && !self.config.keybindings.is_bound(Mode::Examination, key) =>
+                {
+                    if let Some(idx) = option_index(c) {
+                        self.select_option(idx)
+                    }
                 }
-                KeyCode::Right => self.next_tab(),
-                KeyCode::Left => self.previous_tab(),
                 _ => {}
-            }
+            },
+            _ => {}
         }
         Ok(None)
     }
 
-    fn update(&mut self, action: Action) -> Result<Option<Action>> {
-        match action {
-            // 交卷
-            Action::Submit => {
-                // 判断是否全部题目都已经做完，否则弹框提示
-                if self
-                    .questions
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<Option<Action>> {
+        if self.mode_holder.get_mode() != Mode::Examination {
+            return Ok(None);
+        }
+        let position = Position::new(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.list_state.select_next(),
+            MouseEventKind::ScrollUp => self.list_state.select_previous(),
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some((_, question_type)) = self
+                    .tab_areas
                     .iter()
-                    .all(|q| q.1.iter().all(|q| q.answered()))
+                    .find(|(area, _)| area.contains(position))
                 {
-                    self.handle_submit()
-                } else {
-                    Ok(Some(Action::Alert(
-                        "还有题目未做完，是否确认交卷？".to_string(),
-                        ConfirmEvent::Submit,
-                    )))
+                    if *question_type != self.selected_tab {
+                        self.selected_tab = *question_type;
+                        self.list_state.select_first();
+                    }
+                    return Ok(None);
+                }
+                if let Some((_, idx)) = self
+                    .item_areas
+                    .iter()
+                    .find(|(area, _)| area.contains(position))
+                {
+                    let idx = *idx;
+                    self.list_state.select(Some(idx));
+                    // 双击同一道题打开作答弹框
+                    let now = Instant::now();
+                    let double_click = self.last_click.is_some_and(|(at, tab, i)| {
+                        tab == self.selected_tab && i == idx && now - at < DOUBLE_CLICK
+                    });
+                    if double_click {
+                        self.last_click = None;
+                        self.open_answer()?;
+                    } else {
+                        self.last_click = Some((now, self.selected_tab, idx));
+                    }
                 }
             }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        self.track_time(&action);
+        self.notify_viewing();
+        match (self.mode_holder.get_mode(), &action) {
+            (Mode::Examination, Action::ToggleLinear) => {
+                self.linear = !self.linear;
+                self.linear_scroll = 0;
+            }
+            (Mode::Examination, Action::NextQuestion) if self.linear => {
+                self.linear_scroll = self.linear_scroll.saturating_add(1)
+            }
+            (Mode::Examination, Action::PrevQuestion) if self.linear => {
+                self.linear_scroll = self.linear_scroll.saturating_sub(1)
+            }
+            (Mode::Examination, Action::NextQuestion) => self.list_state.select_next(),
+            (Mode::Examination, Action::PrevQuestion) => self.list_state.select_previous(),
+            (Mode::Examination, Action::NextTab) => self.next_tab(),
+            (Mode::Examination, Action::PrevTab) => self.previous_tab(),
+            (Mode::Examination, Action::OpenAnswer) => self.open_answer()?,
+            (Mode::Examination, Action::StartJump) => {
+                self.jump_input.clear();
+                self.mode_holder.set_mode(Mode::Jump);
+            }
+            (Mode::Jump, Action::ConfirmDialog) => self.confirm_jump(),
+            (Mode::Summary, Action::NextQuestion) => self.summary_state.select_next(),
+            (Mode::Summary, Action::PrevQuestion) => self.summary_state.select_previous(),
+            (Mode::Summary, Action::JumpToSelected) => self.jump_to_summary_entry(),
+            (Mode::Summary, Action::ConfirmDialog) => return self.handle_submit(),
+            (Mode::Unlock, Action::ConfirmDialog) => return Ok(self.unlock()),
+            // 已交卷，取消只清空输入，不能回到作答
+            (Mode::Unlock, Action::CancelDialog) => self.password_input.clear(),
+            (Mode::Jump | Mode::Summary, Action::CancelDialog) => {
+                self.mode_holder.set_mode(Mode::Examination)
+            }
+            _ => {}
+        }
+        match action {
+            // 交卷前先展示未答与标记题目的汇总，由用户确认
+            Action::Submit
+                if self.state == State::Ing && self.mode_holder.get_mode() == Mode::Examination =>
+            {
+                self.summary_state = ListState::default();
+                self.mode_holder.set_mode(Mode::Summary);
+                Ok(None)
+            }
             Action::Confirm(ConfirmEvent::Submit) => self.handle_submit(),
+            Action::Graded(score, paper) => {
+                self.questions = paper.into_iter().collect();
+                self.score = Some(score);
+                self.mode_holder.set_mode(Mode::Examination);
+                let msg = i18n::tf("exam.final_score", &[("score", &score)]);
+                Ok(Some(Action::Alert(msg, ConfirmEvent::Score)))
+            }
             Action::Confirm(ConfirmEvent::Score) => {
                 self.state = State::End;
-                self.mode_holder.set_mode(Mode::Examination);
+                Ok(None)
+            }
+            Action::ToggleFlag => {
+                self.toggle_flag();
+                Ok(None)
+            }
+            Action::ToggleExplanation if self.state == State::End => {
+                self.show_explanation = !self.show_explanation;
                 Ok(None)
             }
             _ => Ok(None),
@@ -311,31 +983,493 @@ impl Widget for &mut Examination {
     where
         Self: Sized,
     {
-        if let Ok(q) = self.answer_rx.try_recv() {
-            self.mode_holder.set_mode(Mode::Examination);
-            let current_questions = self.questions.get_mut(&self.selected_tab).unwrap();
-            current_questions[self.list_state.selected().unwrap()] = q;
-        }
+        self.receive_answer();
 
         let vertical = Layout::vertical([Length(1), Length(1), Min(0), Length(1)]);
         let [title_area, tab_area, inner_area, footer_area] = vertical.areas(area);
-        Paragraph::new(self.examination_config.name.clone())
-            .style(Style::default().fg(Color::Yellow))
+        Paragraph::new(self.examination_config.name().to_string())
+            .style(theme::style("title"))
             .alignment(Alignment::Center)
             .render(title_area, buf);
+        self.render_question_timer(title_area, buf);
         self.render_tabs(tab_area, buf);
+        if self.linear {
+            self.item_areas.clear();
+            self.render_linear(inner_area, buf);
+            self.render_footer(footer_area, buf);
+            return;
+        }
+        let explanation_visible = (self.state == State::End && self.show_explanation)
+            || (self.state == State::Ing && self.selected_graded());
+        let inner_area = if explanation_visible {
+            let [list_area, explanation_area] =
+                Layout::vertical([Min(0), Percentage(35)]).areas(inner_area);
+            self.render_explanation(explanation_area, buf);
+            list_area
+        } else {
+            inner_area
+        };
+        // 宽度足够时在右侧显示答题卡
+        let inner_area = if inner_area.width >= 60 {
+            let [list_area, navigator_area] =
+                Layout::horizontal([Min(0), Length(22)]).areas(inner_area);
+            Navigator {
+                questions: &self.questions,
+                flagged: &self.flagged,
+                selected: self.list_state.selected().map(|i| (self.selected_tab, i)),
+            }
+            .render(navigator_area, buf);
+            list_area
+        } else {
+            inner_area
+        };
+        let questions = self.current_questions();
         let question_tab_inner = QuestionTabInner {
-            questions: self.current_questions(),
+            graded: questions.iter().map(|q| self.graded(q)).collect(),
+            notes: (0..questions.len())
+                .map(|i| self.time_note(self.selected_tab, i))
+                .collect(),
+            flagged: (0..questions.len())
+                .map(|i| self.flagged.contains(&(self.selected_tab, i)))
+                .collect(),
+            offset: self.offset(self.selected_tab),
+            questions,
             state: self.state,
-            list_state: self.list_state.clone(),
+            list_state: &mut self.list_state,
+            item_areas: &mut self.item_areas,
         };
         question_tab_inner.render(inner_area, buf);
-        render_footer(footer_area, buf);
+        match self.mode_holder.get_mode() {
+            Mode::Jump => Line::from(i18n::tf(
+                "jump.prompt",
+                &[
+                    ("input", &self.jump_input),
+                    (
+                        "hint",
+                        &help::footer(
+                            &self.config.keybindings,
+                            Mode::Jump,
+                            &[
+                                (Action::ConfirmDialog, "footer.confirm"),
+                                (Action::CancelDialog, "footer.cancel"),
+                            ],
+                        ),
+                    ),
+                ],
+            ))
+            .centered()
+            .render(footer_area, buf),
+            Mode::Unlock => {
+                let prompt = i18n::tf(
+                    "unlock.prompt",
+                    &[
+                        ("input", &"*".repeat(self.password_input.chars().count())),
+                        (
+                            "hint",
+                            &help::footer(
+                                &self.config.keybindings,
+                                Mode::Unlock,
+                                &[
+                                    (Action::ConfirmDialog, "footer.confirm"),
+                                    (Action::CancelDialog, "footer.clear"),
+                                ],
+                            ),
+                        ),
+                    ],
+                );
+                match &self.unlock_error {
+                    Some(error) => Line::from(vec![
+                        Span::styled(format!("{error} "), theme::style("error")),
+                        Span::raw(prompt),
+                    ]),
+                    None => Line::raw(prompt),
+                }
+                .centered()
+                .render(footer_area, buf)
+            }
+            _ => self.render_footer(footer_area, buf),
+        }
+        if self.mode_holder.get_mode() == Mode::Summary {
+            self.render_summary(area, buf);
+        }
     }
 }
 
-pub fn render_footer(area: Rect, buf: &mut Buffer) {
-    Line::raw("◄ ► to change tab | Ctrl+c to quit | Enter to write answer")
-        .centered()
+impl Examination {
+    /// 接收作答弹框确认的答案
+    fn receive_answer(&mut self) {
+        if let Ok(q) = self.answer_rx.try_recv() {
+            self.mode_holder.set_mode(Mode::Examination);
+            let selected = self.list_state.selected().unwrap();
+            let current_questions = self.questions.get_mut(&self.selected_tab).unwrap();
+            current_questions[selected] = q;
+            self.answer_changed(self.selected_tab, selected);
+        }
+    }
+
+    /// 设置了作答时限时，在标题行左侧显示本题的剩余时间
+    fn render_question_timer(&self, area: Rect, buf: &mut Buffer) {
+        let Some(limit) = self.examination_config.question_time_limit() else {
+            return;
+        };
+        let Some(idx) = self.list_state.selected() else {
+            return;
+        };
+        let key = (self.selected_tab, idx);
+        if self.state != State::Ing || self.timed_out.contains(&key) {
+            return;
+        }
+        let spent = self.time_spent.get(&key).copied().unwrap_or_default();
+        let seconds = limit.saturating_sub(spent).as_secs();
+        Line::styled(
+            i18n::tf("timer.question", &[("seconds", &seconds)]),
+            theme::style("timer"),
+        )
         .render(area, buf);
+    }
+
+    /// 底部提示由当前的快捷键绑定生成
+    fn render_footer(&self, area: Rect, buf: &mut Buffer) {
+        let keybindings = &self.config.keybindings;
+        let footer = match self.state {
+            State::Ing => {
+                let footer = help::footer(
+                    keybindings,
+                    Mode::Examination,
+                    &[
+                        (Action::NextTab, "footer.change_tab"),
+                        (Action::OpenAnswer, "footer.write_answer"),
+                        (Action::ToggleFlag, "footer.flag"),
+                        (Action::StartJump, "footer.jump"),
+                        (Action::Submit, "footer.submit"),
+                        (Action::Help, "footer.help"),
+                    ],
+                );
+                format!("{} | {footer}", i18n::t("footer.choose_option"))
+            }
+            State::End => help::footer(
+                keybindings,
+                Mode::Examination,
+                &[
+                    (Action::NextTab, "footer.change_tab"),
+                    (Action::ToggleExplanation, "footer.toggle_explanation"),
+                    (Action::Help, "footer.help"),
+                    (Action::Quit, "footer.quit"),
+                ],
+            ),
+        };
+        Line::raw(footer).centered().render(area, buf);
+    }
+
+    /// 纯文本视图：按试卷顺序排列全部题目及解析，无障碍模式下去掉所有样式
+    fn render_linear(&mut self, area: Rect, buf: &mut Buffer) {
+        let mut lines = vec![];
+        let mut number = 0;
+        for (question_type, questions) in self.questions.iter() {
+            if questions.is_empty() {
+                continue;
+            }
+            lines.push(Line::styled(question_type.label(), question_type.accent()));
+            for (i, q) in questions.iter().enumerate() {
+                let state = if self.graded(q) {
+                    State::End
+                } else {
+                    self.state
+                };
+                let mut text = q.convert_text(state, number);
+                if let Some(note) = self.time_note(*question_type, i)
+                    && let Some(line) = text.lines.first_mut()
+                {
+                    line.spans.push(Span::raw("  "));
+                    line.spans.push(note);
+                }
+                lines.extend(text.lines);
+                if state == State::End {
+                    lines.extend(q.explanation_text().lines);
+                }
+                lines.push(Line::default());
+                number += 1;
+            }
+        }
+        let text = markup::wrap(Text::from(lines), area.width as usize);
+        let text = if theme::accessible() {
+            Text::from(
+                text.lines
+                    .into_iter()
+                    .map(|line| Line::raw(line.to_string()))
+                    .collect::<Vec<Line>>(),
+            )
+        } else {
+            text
+        };
+        // 不能滚动到内容之外
+        self.linear_scroll = self
+            .linear_scroll
+            .min(text.height().saturating_sub(area.height as usize) as u16);
+        Paragraph::new(text)
+            .scroll((self.linear_scroll, 0))
+            .render(area, buf);
+    }
+
+    fn render_explanation(&self, area: Rect, buf: &mut Buffer) {
+        let text = self
+            .list_state
+            .selected()
+            .and_then(|idx| self.current_questions().get(idx).cloned())
+            .map(|q| q.explanation_text())
+            .filter(|text| !text.lines.is_empty())
+            .unwrap_or_else(|| Text::from(i18n::t("explanation.none")));
+        Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .title(i18n::t("explanation.title"))
+                    .borders(Borders::TOP)
+                    .border_style(self.selected_tab.accent()),
+            )
+            .render(area, buf);
+    }
+}
+
+/// 选项快捷键：A-H 或 1-8
+pub fn option_index(c: char) -> Option<usize> {
+    match c {
+        'a'..='h' => Some(c as usize - 'a' as usize),
+        'A'..='H' => Some(c as usize - 'A' as usize),
+        '1'..='8' => Some(c as usize - '1' as usize),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::action::{Action, ConfirmEvent};
+    use crate::app::{Mode, ModeHolder, ModeHolderLock};
+    use crate::components::Component;
+    use crate::components::examination::{Examination, Paper, QuestionType, State};
+    use crate::config::Config;
+    use crossterm::event::{
+        KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::widgets::Widget;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+    use tokio::sync::mpsc;
+
+    const EXAM: &str =
+        r#"{"name":"quiz","duration":60,"single_select":2,"multi_select":0,"judge":2,"fill_in":0}"#;
+
+    /// 单选题与判断题各两道的试卷
+    fn examination() -> Examination {
+        examination_with(
+            EXAM,
+            r#"[
+                ["SingleSelect",[
+                    {"SingleSelect":{"question":"1 + 1 = ( )","options":["A: 1","B: 2"],"answer":"B","score":2,"explanation":"one plus one is two","reference":"arithmetic"}},
+                    {"SingleSelect":{"question":"2 + 2 = ( )","options":["A: 4","B: 5"],"answer":"A","score":2}}
+                ]],
+                ["Judge",[
+                    {"Judge":{"question":"2 > 3 ( )","answer":"No","score":1}},
+                    {"Judge":{"question":"3 > 2 ( )","answer":"Yes","score":1}}
+                ]]
+            ]"#,
+        )
+    }
+
+    fn examination_with(exam: &str, paper: &str) -> Examination {
+        let (question_tx, _) = mpsc::unbounded_channel();
+        let (_, answer_rx) = mpsc::unbounded_channel();
+        let exam = serde_json::from_str(exam).unwrap();
+        let paper: Paper = serde_json::from_str(paper).unwrap();
+        Examination::with_questions(
+            question_tx,
+            answer_rx,
+            ModeHolderLock(Arc::new(Mutex::new(ModeHolder::default()))),
+            Config::default(),
+            exam,
+            paper.into_iter().collect(),
+        )
+    }
+
+    fn render(examination: &mut Examination, area: Rect) -> String {
+        let mut buf = Buffer::empty(area);
+        examination.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn test_toggle_explanation() {
+        let mut examination = examination();
+        // 交卷前不能查看解析
+        examination.update(Action::ToggleExplanation).unwrap();
+        assert!(!examination.show_explanation);
+
+        examination
+            .update(Action::Confirm(ConfirmEvent::Score))
+            .unwrap();
+        assert!(examination.state == State::End);
+        let area = Rect::new(0, 0, 80, 24);
+        assert!(!render(&mut examination, area).contains("one plus one is two"));
+        examination.update(Action::ToggleExplanation).unwrap();
+        let screen = render(&mut examination, area);
+        assert!(screen.contains("one plus one is two"));
+        assert!(screen.contains("arithmetic"));
+    }
+
+    #[test]
+    fn test_practice_graded_when_complete() {
+        let mut examination = examination_with(
+            r#"{"name":"quiz","duration":60,"single_select":0,"multi_select":0,"judge":0,"fill_in":1,"practice":true,"lock_answer":true}"#,
+            r#"[["FillIn",[{"FillIn":{"question":"{} + {} = 3","items":[{"answer":"1","score":1},{"answer":"2","score":1}]}}]]]"#,
+        );
+        examination.selected_tab = QuestionType::FillIn;
+        let fill = |examination: &mut Examination, input: Vec<Option<&str>>| {
+            examination
+                .questions
+                .get_mut(&QuestionType::FillIn)
+                .unwrap()[0]
+                .set_user_input(input.into_iter().map(|s| s.map(String::from)).collect());
+        };
+        // 只填了一空时不判分，也不锁定
+        fill(&mut examination, vec![Some("1"), None]);
+        assert!(!examination.selected_graded());
+        fill(&mut examination, vec![Some("1"), Some("2")]);
+        assert!(examination.selected_graded());
+    }
+
+    #[test]
+    fn test_jump_to() {
+        let mut examination = examination();
+        assert_eq!(examination.offset(QuestionType::Judge), 2);
+        examination.jump_to(3);
+        assert_eq!(
+            (examination.selected_tab, examination.list_state.selected()),
+            (QuestionType::Judge, Some(0))
+        );
+        // 超出题号范围时不跳转
+        examination.jump_to(5);
+        assert_eq!(
+            (examination.selected_tab, examination.list_state.selected()),
+            (QuestionType::Judge, Some(0))
+        );
+        examination.jump_to(0);
+        assert_eq!(
+            (examination.selected_tab, examination.list_state.selected()),
+            (QuestionType::SingleSelect, Some(0))
+        );
+
+        examination.update(Action::StartJump).unwrap();
+        assert_eq!(examination.mode_holder.get_mode(), Mode::Jump);
+        examination.handle_jump_key(KeyEvent::from(KeyCode::Char('4')));
+        examination.handle_jump_key(KeyEvent::from(KeyCode::Char('x')));
+        examination.update(Action::ConfirmDialog).unwrap();
+        assert_eq!(examination.mode_holder.get_mode(), Mode::Examination);
+        assert_eq!(
+            (examination.selected_tab, examination.list_state.selected()),
+            (QuestionType::Judge, Some(1))
+        );
+    }
+
+    #[test]
+    fn test_question_time_limit() {
+        let mut examination = examination();
+        examination.examination_config = serde_json::from_str(
+            &EXAM.replace(r#""fill_in":0"#, r#""fill_in":0,"question_time_limit":1"#),
+        )
+        .unwrap();
+        let (command_tx, mut command_rx) = mpsc::unbounded_channel();
+        examination.command_tx = Some(command_tx);
+        examination.jump_to(1);
+        // 第一题用时超过限制后关闭作答弹框并自动跳到下一题
+        examination.mode_holder.set_mode(Mode::Input);
+        examination.last_tick = Instant::now() - Duration::from_secs(2);
+        examination.update(Action::Tick).unwrap();
+        assert_eq!(examination.mode_holder.get_mode(), Mode::Examination);
+        let mut actions = std::iter::from_fn(|| command_rx.try_recv().ok());
+        assert!(actions.any(|action| action == Action::DiscardInput));
+        assert_eq!(
+            (examination.selected_tab, examination.list_state.selected()),
+            (QuestionType::SingleSelect, Some(1))
+        );
+        assert!(
+            examination
+                .timed_out
+                .contains(&(QuestionType::SingleSelect, 0))
+        );
+
+        // 回到超时的题目后不能再作答
+        examination.jump_to(1);
+        examination.select_option(1);
+        assert!(!examination.current_questions()[0].answered());
+        // 试题通道的接收端已关闭，未被拦截时发送会出错
+        assert!(examination.open_answer().is_ok());
+    }
+
+    #[test]
+    fn test_summary_entries() {
+        let mut examination = examination();
+        examination
+            .questions
+            .get_mut(&QuestionType::SingleSelect)
+            .unwrap()[0]
+            .toggle_option(1);
+        examination.jump_to(4);
+        examination.toggle_flag();
+        examination.questions.get_mut(&QuestionType::Judge).unwrap()[1]
+            .set_user_input(vec![Some("Yes".to_string())]);
+        // 已答且未标记的第 1 题不列出，题号按全卷连续编号
+        assert_eq!(
+            examination.summary_entries(),
+            vec![
+                (QuestionType::SingleSelect, 1, 2, false, false),
+                (QuestionType::Judge, 0, 3, false, false),
+                (QuestionType::Judge, 1, 4, true, true),
+            ]
+        );
+
+        examination.jump_to(1);
+        examination.update(Action::Submit).unwrap();
+        assert_eq!(examination.mode_holder.get_mode(), Mode::Summary);
+        examination.update(Action::NextQuestion).unwrap();
+        examination.update(Action::NextQuestion).unwrap();
+        examination.update(Action::JumpToSelected).unwrap();
+        assert_eq!(examination.mode_holder.get_mode(), Mode::Examination);
+        assert_eq!(
+            (examination.selected_tab, examination.list_state.selected()),
+            (QuestionType::Judge, Some(0))
+        );
+    }
+
+    #[test]
+    fn test_mouse_hit_testing() {
+        let mut examination = examination();
+        let area = Rect::new(0, 0, 80, 24);
+        render(&mut examination, area);
+        let click = |examination: &mut Examination, column, row| {
+            let mouse = MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            };
+            examination.handle_mouse_event(mouse).unwrap();
+        };
+        // 点击第二道题
+        let (item, _) = examination.item_areas[1];
+        click(&mut examination, item.x + 2, item.y);
+        assert_eq!(examination.list_state.selected(), Some(1));
+
+        // 点击标签之间的分隔符不切换
+        let (tab, question_type) = examination.tab_areas[2];
+        assert_eq!(question_type, QuestionType::Judge);
+        click(&mut examination, tab.x - 1, tab.y);
+        assert_eq!(examination.selected_tab, QuestionType::SingleSelect);
+        click(&mut examination, tab.right() - 1, tab.y);
+        assert_eq!(
+            (examination.selected_tab, examination.list_state.selected()),
+            (QuestionType::Judge, Some(0))
+        );
+    }
 }
@@ -0,0 +1,371 @@
+//! 题干与选项的多行排版：识别 ``` 围起来的代码块，保留缩进并做简单的语法高亮，
+//! 同时按显示宽度折行，保证在列表中能正确换行。
+
+use crate::theme;
+use examination::engine::code::is_fence;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span, Text};
+use std::sync::LazyLock;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+static CODE_STYLE: LazyLock<Style, fn() -> Style> = LazyLock::new(|| theme::style("code"));
+static FENCE_STYLE: LazyLock<Style, fn() -> Style> = LazyLock::new(|| theme::style("code.fence"));
+static KEYWORD_STYLE: LazyLock<Style, fn() -> Style> =
+    LazyLock::new(|| CODE_STYLE.patch(theme::style("code.keyword")));
+static STRING_STYLE: LazyLock<Style, fn() -> Style> =
+    LazyLock::new(|| CODE_STYLE.patch(theme::style("code.string")));
+static NUMBER_STYLE: LazyLock<Style, fn() -> Style> =
+    LazyLock::new(|| CODE_STYLE.patch(theme::style("code.number")));
+static COMMENT_STYLE: LazyLock<Style, fn() -> Style> =
+    LazyLock::new(|| CODE_STYLE.patch(theme::style("code.comment")));
+
+/// 常见语言关键字的并集，只用于高亮
+const KEYWORDS: &[&str] = &[
+    "as",
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "def",
+    "default",
+    "do",
+    "elif",
+    "else",
+    "enum",
+    "except",
+    "false",
+    "False",
+    "finally",
+    "fn",
+    "for",
+    "from",
+    "func",
+    "function",
+    "if",
+    "impl",
+    "import",
+    "in",
+    "interface",
+    "let",
+    "loop",
+    "match",
+    "mod",
+    "mut",
+    "new",
+    "None",
+    "null",
+    "package",
+    "pass",
+    "pub",
+    "raise",
+    "return",
+    "self",
+    "Self",
+    "static",
+    "struct",
+    "switch",
+    "this",
+    "throw",
+    "trait",
+    "true",
+    "True",
+    "try",
+    "type",
+    "use",
+    "var",
+    "void",
+    "where",
+    "while",
+    "with",
+    "yield",
+];
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// 按换行符把若干 span 拆成多行，并对代码块内的行应用代码样式
+pub fn to_lines(spans: Vec<Span<'static>>) -> Vec<Line<'static>> {
+    to_marked_lines(spans.into_iter().map(|span| (span, false)).collect())
+}
+
+/// 同 [`to_lines`]，标记为 true 的 span（如用户作答）在代码块中也保持原样，不做高亮
+pub fn to_marked_lines(spans: Vec<(Span<'static>, bool)>) -> Vec<Line<'static>> {
+    let mut raw_lines: Vec<Vec<(Span<'static>, bool)>> = vec![vec![]];
+    for (span, marked) in spans {
+        let style = span.style;
+        // 制表符没有固定的显示宽度，统一展开为空格
+        let content = span.content.replace('\t', "    ");
+        let mut parts = content.split('\n');
+        if let Some(first) = parts.next()
+            && !first.is_empty()
+        {
+            raw_lines
+                .last_mut()
+                .unwrap()
+                .push((Span::styled(first.to_string(), style), marked));
+        }
+        for part in parts {
+            let mut line = vec![];
+            if !part.is_empty() {
+                line.push((Span::styled(part.to_string(), style), marked));
+            }
+            raw_lines.push(line);
+        }
+    }
+
+    let mut lines = vec![];
+    let mut in_code = false;
+    for spans in raw_lines {
+        let content = spans
+            .iter()
+            .map(|(s, _)| s.content.as_ref())
+            .collect::<String>();
+        if is_fence(&content) {
+            in_code = !in_code;
+            lines.push(Line::from(Span::styled(content, *FENCE_STYLE)));
+        } else if in_code {
+            lines.push(Line::from(highlight(spans)));
+        } else {
+            lines.push(Line::from(
+                spans.into_iter().map(|(s, _)| s).collect::<Vec<_>>(),
+            ));
+        }
+    }
+    lines
+}
+
+/// 对代码行做简单的词法高亮，标记的 span 保持原样
+fn highlight(spans: Vec<(Span<'static>, bool)>) -> Vec<Span<'static>> {
+    let mut result = vec![];
+    for (span, marked) in spans {
+        if marked {
+            result.push(span);
+        } else {
+            result.extend(highlight_code(&span.content));
+        }
+    }
+    result
+}
+
+fn highlight_code(code: &str) -> Vec<Span<'static>> {
+    let chars = code.chars().collect::<Vec<char>>();
+    let mut spans = vec![];
+    let mut i = 0;
+    let mut plain = String::new();
+    let flush = |plain: &mut String, spans: &mut Vec<Span<'static>>| {
+        if !plain.is_empty() {
+            spans.push(Span::styled(std::mem::take(plain), *CODE_STYLE));
+        }
+    };
+    while i < chars.len() {
+        let c = chars[i];
+        let rest = &chars[i..];
+        if rest.starts_with(&['/', '/']) || (c == '#' && !rest.starts_with(&['#', '['])) {
+            flush(&mut plain, &mut spans);
+            spans.push(Span::styled(
+                chars[i..].iter().collect::<String>(),
+                *COMMENT_STYLE,
+            ));
+            break;
+        } else if c == '"' || c == '\'' {
+            flush(&mut plain, &mut spans);
+            let mut end = i + 1;
+            while end < chars.len() && chars[end] != c {
+                if chars[end] == '\\' {
+                    end += 1;
+                }
+                end += 1;
+            }
+            let end = (end + 1).min(chars.len());
+            spans.push(Span::styled(
+                chars[i..end].iter().collect::<String>(),
+                *STRING_STYLE,
+            ));
+            i = end;
+        } else if c.is_alphanumeric() || c == '_' {
+            let mut end = i;
+            while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+                end += 1;
+            }
+            let word = chars[i..end].iter().collect::<String>();
+            if KEYWORDS.contains(&word.as_str()) {
+                flush(&mut plain, &mut spans);
+                spans.push(Span::styled(word, *KEYWORD_STYLE));
+            } else if c.is_ascii_digit() {
+                flush(&mut plain, &mut spans);
+                spans.push(Span::styled(word, *NUMBER_STYLE));
+            } else {
+                plain.push_str(&word);
+            }
+            i = end;
+        } else {
+            plain.push(c);
+            i += 1;
+        }
+    }
+    flush(&mut plain, &mut spans);
+    spans
+}
+
+/// 按显示宽度折行，续行沿用原行的前导空白，代码缩进因此得以保留
+pub fn wrap(text: Text<'_>, width: usize) -> Text<'_> {
+    if width == 0 {
+        return text;
+    }
+    let mut lines = vec![];
+    for line in text.lines {
+        lines.extend(wrap_line(line, width));
+    }
+    Text::from(lines)
+}
+
+fn wrap_line(line: Line<'_>, width: usize) -> Vec<Line<'_>> {
+    if line.width() <= width {
+        return vec![line];
+    }
+    let content = line
+        .spans
+        .iter()
+        .map(|s| s.content.as_ref())
+        .collect::<String>();
+    let indent = content
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect::<String>();
+    let indent = if indent.width() * 2 > width {
+        String::new()
+    } else {
+        indent
+    };
+    // 全角空格等缩进字符占两列，按显示宽度计算
+    let indent_width = indent.width();
+    let indent_style = line.spans.first().map(|s| s.style).unwrap_or_default();
+
+    // 以“单词”为单位排版：连续的 ASCII 字母数字为一个单词，其余字符各自成词
+    let mut words: Vec<(Style, String, usize)> = vec![];
+    for span in &line.spans {
+        for c in span.content.chars() {
+            let w = c.width().unwrap_or(0);
+            match words.last_mut() {
+                Some((style, word, word_width))
+                    if *style == span.style
+                        && is_word_char(c)
+                        && word.chars().last().is_some_and(is_word_char) =>
+                {
+                    word.push(c);
+                    *word_width += w;
+                }
+                _ => words.push((span.style, c.to_string(), w)),
+            }
+        }
+    }
+
+    let mut lines = vec![];
+    let mut current: Vec<Span<'static>> = vec![];
+    let mut current_width = 0;
+    let new_line = |lines: &mut Vec<Line<'static>>, current: &mut Vec<Span<'static>>| {
+        lines.push(Line::from(std::mem::take(current)));
+    };
+    for (style, word, word_width) in words {
+        if current_width + word_width > width && current_width > indent_width {
+            new_line(&mut lines, &mut current);
+            current.push(Span::styled(indent.clone(), indent_style));
+            current_width = indent_width;
+            if word.trim().is_empty() {
+                continue;
+            }
+        }
+        if current_width + word_width > width {
+            // 单词本身超过一行，逐字符拆分
+            for c in word.chars() {
+                let w = c.width().unwrap_or(0);
+                if current_width + w > width && current_width > indent_width {
+                    new_line(&mut lines, &mut current);
+                    current.push(Span::styled(indent.clone(), indent_style));
+                    current_width = indent_width;
+                }
+                current.push(Span::styled(c.to_string(), style));
+                current_width += w;
+            }
+        } else {
+            current.push(Span::styled(word, style));
+            current_width += word_width;
+        }
+    }
+    if !current.is_empty() {
+        new_line(&mut lines, &mut current);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod test {
+    use crate::components::examination::markup::{to_lines, to_marked_lines, wrap};
+    use ratatui::style::{Color, Style};
+    use ratatui::text::{Line, Span, Text};
+
+    #[test]
+    fn test_wrap_keeps_indent() {
+        let lines = to_lines(vec![Span::raw(
+            "```\n    let value = some_function(argument);\n```",
+        )]);
+        let text = wrap(Text::from(lines), 24);
+        let contents = text
+            .lines
+            .iter()
+            .map(|l| {
+                l.spans
+                    .iter()
+                    .map(|s| s.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>();
+        assert_eq!(
+            contents,
+            vec![
+                "```",
+                "    let value = ",
+                "    some_function(",
+                "    argument);",
+                "```"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_highlight_marked() {
+        // 题干带有主题样式时仍做高亮，只有标记的作答内容保持原样
+        let stem = Style::new().fg(Color::White);
+        let answer = Style::new().fg(Color::Green);
+        let lines = to_marked_lines(vec![
+            (Span::styled("```\nlet x = ", stem), false),
+            (Span::styled("let", answer), true),
+            (Span::styled("\n```", stem), false),
+        ]);
+        let spans = &lines[1].spans;
+        assert_eq!(spans[0].content, "let");
+        assert_ne!(spans[0].style, stem);
+        let last = spans.last().unwrap();
+        assert_eq!((last.content.as_ref(), last.style), ("let", answer));
+    }
+
+    #[test]
+    fn test_wrap_wide_indent() {
+        // 两个全角空格占四列
+        let text = wrap(Text::from(Line::raw("\u{3000}\u{3000}abc def gh")), 10);
+        let contents = text
+            .lines
+            .iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(
+            contents,
+            vec!["\u{3000}\u{3000}abc ", "\u{3000}\u{3000}def gh"]
+        );
+    }
+}
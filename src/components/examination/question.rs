@@ -1,21 +1,20 @@
-use crate::components::examination::{ExaminationConfig, QuestionEnum, QuestionType, State};
-use crate::config::Config;
-use linked_hash_map::LinkedHashMap;
-use rand::rng;
-use rand::seq::IndexedRandom;
+//! 题目在终端界面中的显示：题干、选项与作答内容的样式，以及作答弹框的输入提示。
+
+use crate::components::examination::markup;
+use crate::components::examination::{QuestionEnum, State};
+use crate::{i18n, theme};
+use examination::engine::blank::{self, Blank, Stem};
+use examination::engine::question::{
+    FillIn, Group, InputError, InputKind, Judge, MultiSelect, Question, SingleSelect, letter_range,
+    to_idx,
+};
 use ratatui::prelude::{Line, Text};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::Style;
 use ratatui::text::Span;
-use regex::Regex;
-use serde::{Deserialize, Serialize};
-use std::cmp::min;
-use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::Read;
 use std::sync::LazyLock;
-use std::vec;
 
-pub trait Question {
+/// 题目在终端中的显示，判分逻辑见 [`Question`]
+pub trait QuestionText: Question {
     fn convert_text(&self, state: State, q_index: usize) -> Text<'_>;
 
     fn option_style(
@@ -50,90 +49,78 @@ pub trait Question {
         }
     }
 
-    fn cal_score(&self) -> u16 {
-        match self.user_input() {
-            None => 0,
-            Some(user_input) => {
-                if Self::check_answer(user_input, self.answer()) {
-                    self.score()
-                } else {
-                    0
-                }
-            }
+    /// 无障碍模式下交卷后在选项后附加的文字标记
+    fn option_label(
+        &self,
+        state: State,
+        i: usize,
+        user_input_idx: &Option<Vec<usize>>,
+        answer_idx: &[usize],
+    ) -> Option<&'static str> {
+        if state != State::End || !theme::accessible() {
+            return None;
+        }
+        let chosen = user_input_idx.as_ref().is_some_and(|idx| idx.contains(&i));
+        match (chosen, answer_idx.contains(&i)) {
+            (true, true) => Some(i18n::t("answer.right_option")),
+            (true, false) => Some(i18n::t("answer.wrong_option")),
+            (false, true) => Some(i18n::t("answer.correct_option")),
+            (false, false) => None,
         }
     }
 
-    fn check_answer(user_input: String, answer: String) -> bool {
-        let user_input_set = user_input
-            .chars()
-            .map(|c| c.to_lowercase().to_string())
-            .collect::<HashSet<_>>();
-        let answer_set = answer
-            .chars()
-            .map(|c| c.to_lowercase().to_string())
-            .collect::<HashSet<_>>();
-        user_input_set == answer_set
-    }
-
-    fn user_input(&self) -> Option<String>;
-
-    fn answer(&self) -> String;
-    fn question(&self) -> String;
-
-    fn score(&self) -> u16;
-
-    fn answered(&self) -> bool;
-
-    fn convert_question(&self, state: State, q_index: usize) -> Line {
-        let question = self.question();
-        let lang = Lang::check(&question);
-        let line = match self.user_input() {
-            Some(user_input) => {
-                let vec = lang
-                    .pattern()
-                    .split(&question)
-                    .map(|s| s.to_string())
-                    .collect::<Vec<String>>();
-                let spans: Vec<Span> = vec![
-                    vec![Span::from(format!("{}: {}", q_index + 1, vec[0].clone()))],
-                    self.user_input_span(state, user_input.to_string(), self.answer(), lang),
-                    vec![Span::from(vec[1].clone())],
-                ]
-                .into_iter()
-                .flatten()
-                .collect();
-                Line::from(spans)
-            }
-            None => Line::from(format!("{}: {question}", q_index + 1)),
+    /// 作答内容显示在第一个填空位置，题干中没有填空位置时显示在末尾
+    fn convert_question(&self, state: State, q_index: usize) -> Vec<Line<'static>> {
+        let stem = blank::parse(&self.question());
+        let blank = stem.default_blank();
+        let input = match self.user_input() {
+            Some(user_input) => self.user_input_span(state, user_input, self.answer(), blank),
+            None => empty_blank(blank),
         };
-        line
+        markup::to_marked_lines(fill_stem(q_index, stem, vec![input]))
     }
     fn user_input_span(
         &self,
         state: State,
         user_input: String,
         answer: String,
-        lang: Lang,
+        blank: Blank,
     ) -> Vec<Span<'static>> {
         match state {
             State::Ing => vec![
-                Span::styled(lang.parentheses().0.clone(), *DEFAULT_STYLE),
+                Span::styled(blank.open, *DEFAULT_STYLE),
                 Span::styled(user_input, *ING_STYLE),
-                Span::styled(lang.parentheses().1, *DEFAULT_STYLE),
+                Span::styled(blank.close, *DEFAULT_STYLE),
             ],
+            // 无障碍模式下不依赖颜色与删除线，用文字说明对错
+            State::End if theme::accessible() => {
+                let label = if Self::check_answer(user_input.clone(), answer.clone()) {
+                    i18n::tf("answer.right", &[("input", &user_input)])
+                } else {
+                    i18n::tf(
+                        "answer.wrong",
+                        &[("input", &user_input), ("answer", &answer)],
+                    )
+                };
+                vec![
+                    Span::styled(blank.open, *DEFAULT_STYLE),
+                    Span::styled(label, *DEFAULT_STYLE),
+                    Span::styled(blank.close, *DEFAULT_STYLE),
+                ]
+            }
             State::End => {
                 if Self::check_answer(user_input.clone(), answer.clone()) {
                     vec![
-                        Span::styled(lang.parentheses().0, *DEFAULT_STYLE),
+                        Span::styled(blank.open, *DEFAULT_STYLE),
                         Span::styled(user_input, *RIGHT_STYLE),
-                        Span::styled(lang.parentheses().1, *DEFAULT_STYLE),
+                        Span::styled(blank.close, *DEFAULT_STYLE),
                     ]
                 } else {
                     vec![
-                        Span::styled(lang.parentheses().0, *DEFAULT_STYLE),
+                        Span::styled(blank.open, *DEFAULT_STYLE),
                         Span::styled(user_input, *WRONG_STYLE),
                         Span::styled(answer.clone(), *RIGHT_STYLE),
-                        Span::styled(lang.parentheses().1, *DEFAULT_STYLE),
+                        Span::styled(blank.close, *DEFAULT_STYLE),
                     ]
                 }
             }
@@ -141,177 +128,66 @@ pub trait Question {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
-pub struct SingleSelect {
-    pub question: String,
-    pub options: Vec<String>,
-    pub answer: String,
-    pub user_input: Option<String>,
-    pub score: u16,
-}
-
-#[derive(Serialize, Deserialize, Clone)]
-pub struct MultiSelect {
-    pub question: String,
-    pub options: Vec<String>,
-    pub answer: String,
-    pub user_input: Option<String>,
-    pub score: u16,
-}
-
-#[derive(Serialize, Deserialize, Clone)]
-pub struct Judge {
-    pub question: String,
-    pub answer: String,
-    pub user_input: Option<String>,
-    pub score: u16,
-}
-
-#[derive(Serialize, Deserialize, Clone)]
-pub struct FillIn {
-    pub question: String,
-    pub items: Vec<FillInItem>,
-}
-
-#[derive(Serialize, Deserialize, Clone)]
-pub struct FillInItem {
-    pub answer: String,
-    pub user_input: Option<String>,
-    pub score: u16,
-}
+/// 整道题目在终端中的显示
+pub trait QuestionView {
+    fn convert_text(&self, state: State, q_index: usize) -> Text<'_>;
 
-#[derive(Serialize, Deserialize)]
-struct Examination {
-    name: String,
-    single_select: usize,
-    multi_select: usize,
-    judge: usize,
-    fill_in: usize,
+    /// 解析面板的内容，材料题会依次附上各子题的解析
+    fn explanation_text(&self) -> Text<'static>;
 }
 
-impl QuestionEnum {
-    pub(crate) fn load(
-        config: Config,
-        ec: ExaminationConfig,
-    ) -> LinkedHashMap<QuestionType, Vec<QuestionEnum>> {
-        let mut questions = String::new();
-        File::open(config.config.data_dir.join("question.json"))
-            .unwrap()
-            .read_to_string(&mut questions)
-            .expect("Fail to load question!");
-        let type_2_questions =
-            serde_json::from_slice::<HashMap<QuestionType, Vec<QuestionEnum>>>(questions.as_ref())
-                .unwrap();
-        let mut questions = LinkedHashMap::new();
-        let single_select: Vec<QuestionEnum> = Self::random_choose_question(
-            &type_2_questions,
-            QuestionType::SingleSelect,
-            ec.single_select,
-        );
-        questions.insert(QuestionType::SingleSelect, single_select);
-        let multi_select: Vec<QuestionEnum> = Self::random_choose_question(
-            &type_2_questions,
-            QuestionType::MultiSelect,
-            ec.multi_select,
-        );
-        questions.insert(QuestionType::MultiSelect, multi_select);
-        let judge: Vec<QuestionEnum> =
-            Self::random_choose_question(&type_2_questions, QuestionType::Judge, ec.judge);
-        questions.insert(QuestionType::Judge, judge);
-        let fill_in: Vec<QuestionEnum> =
-            Self::random_choose_question(&type_2_questions, QuestionType::FillIn, ec.fill_in);
-        questions.insert(QuestionType::FillIn, fill_in);
-        questions
-    }
-
-    fn random_choose_question(
-        question_name_2_vec: &HashMap<QuestionType, Vec<QuestionEnum>>,
-        question_type: QuestionType,
-        question_size: usize,
-    ) -> Vec<QuestionEnum> {
-        question_name_2_vec
-            .get(&question_type)
-            .map(|v| {
-                let n = min(v.len(), question_size);
-                let mut rng = rng();
-                v.choose_multiple(&mut rng, n).cloned().collect()
-            })
-            .unwrap_or_default()
-    }
-
-    pub fn convert_text(&self, state: State, q_index: usize) -> Text<'_> {
+impl QuestionView for QuestionEnum {
+    fn convert_text(&self, state: State, q_index: usize) -> Text<'_> {
         match self {
             QuestionEnum::SingleSelect(q) => q.convert_text(state, q_index),
             QuestionEnum::MultiSelect(q) => q.convert_text(state, q_index),
             QuestionEnum::Judge(q) => q.convert_text(state, q_index),
             QuestionEnum::FillIn(q) => q.convert_text(state, q_index),
+            QuestionEnum::Group(q) => group_text(q, state, q_index),
         }
     }
 
-    pub fn user_input(&self) -> Vec<Option<String>> {
-        match self {
-            QuestionEnum::SingleSelect(q) => vec![q.user_input()],
-            QuestionEnum::MultiSelect(q) => vec![q.user_input()],
-            QuestionEnum::Judge(q) => vec![q.user_input()],
-            QuestionEnum::FillIn(q) => q.user_input(),
+    fn explanation_text(&self) -> Text<'static> {
+        let mut lines = vec![];
+        if let Some(explanation) = self.explanation() {
+            lines.extend(markup::to_lines(vec![Span::from(explanation)]));
         }
-    }
-
-    pub fn answered(&self) -> bool {
-        match self {
-            QuestionEnum::SingleSelect(q) => q.answered(),
-            QuestionEnum::MultiSelect(q) => q.answered(),
-            QuestionEnum::Judge(q) => q.answered(),
-            QuestionEnum::FillIn(q) => q.answered(),
+        if let Some(reference) = self.reference() {
+            lines.push(Line::from(Span::styled(
+                i18n::tf("explanation.reference", &[("reference", &reference)]),
+                *REFERENCE_STYLE,
+            )));
         }
-    }
-
-    pub fn set_user_input(&mut self, user_input: Vec<Option<String>>) {
-        match self {
-            QuestionEnum::SingleSelect(q) => {
-                q.user_input = user_input[0].clone();
-            }
-            QuestionEnum::MultiSelect(q) => {
-                q.user_input = user_input[0].clone();
-            }
-            QuestionEnum::Judge(q) => {
-                q.user_input = user_input[0].clone();
-            }
-            QuestionEnum::FillIn(q) => {
-                q.items
-                    .iter_mut()
-                    .zip(user_input.iter())
-                    .for_each(|(item, user_input)| item.user_input = user_input.clone());
+        if let QuestionEnum::Group(group) = self {
+            for (i, q) in group.questions.iter().enumerate() {
+                let child = q.explanation_text();
+                if child.lines.is_empty() {
+                    continue;
+                }
+                lines.push(Line::from(i18n::tf(
+                    "explanation.child",
+                    &[("number", &(i + 1).to_string())],
+                )));
+                lines.extend(child.lines);
             }
         }
-    }
-
-    pub fn input_size(&self) -> usize {
-        match self {
-            QuestionEnum::SingleSelect(_) => 1,
-            QuestionEnum::MultiSelect(_) => 1,
-            QuestionEnum::Judge(_) => 1,
-            QuestionEnum::FillIn(q) => q.items.len(),
-        }
+        Text::from(lines)
     }
 }
 
-static DEFAULT_STYLE: LazyLock<Style, fn() -> Style> = LazyLock::new(Style::default);
+static DEFAULT_STYLE: LazyLock<Style, fn() -> Style> = LazyLock::new(|| theme::style("question"));
 static ING_STYLE: LazyLock<Style, fn() -> Style> =
-    LazyLock::new(|| Style::default().fg(Color::Yellow));
+    LazyLock::new(|| theme::style("question.answering"));
 static RIGHT_STYLE: LazyLock<Style, fn() -> Style> =
-    LazyLock::new(|| Style::default().fg(Color::Green));
-static WRONG_STYLE: LazyLock<Style, fn() -> Style> = LazyLock::new(|| {
-    Style::default()
-        .fg(Color::Red)
-        .add_modifier(Modifier::CROSSED_OUT)
-});
+    LazyLock::new(|| theme::style("question.right"));
+static WRONG_STYLE: LazyLock<Style, fn() -> Style> =
+    LazyLock::new(|| theme::style("question.wrong"));
+static REFERENCE_STYLE: LazyLock<Style, fn() -> Style> =
+    LazyLock::new(|| theme::style("question.reference"));
 
-impl Question for SingleSelect {
+impl QuestionText for SingleSelect {
     fn convert_text(&self, state: State, q_index: usize) -> Text<'_> {
-        let mut lines = vec![];
-        let line = self.convert_question(state, q_index);
-        lines.push(line);
+        let mut lines = self.convert_question(state, q_index);
         for (i, option) in self.options.iter().enumerate() {
             let user_input_idx = self
                 .user_input
@@ -319,39 +195,19 @@ impl Question for SingleSelect {
                 .and_then(|user_input| to_idx(user_input.as_str()).map(|i| vec![i]));
             let answer_idx = to_idx(self.answer.clone().as_str())
                 .map(|i| vec![i])
-                .unwrap();
+                .unwrap_or_default();
+            let marker = radio(user_input_idx.as_ref().is_some_and(|idx| idx.contains(&i)));
+            let label = self.option_label(state, i, &user_input_idx, &answer_idx);
             let style = self.option_style(state, i, user_input_idx, answer_idx);
-            lines.push(Line::from(Span::styled(format!("  {option}"), style)));
+            lines.extend(labeled(option_lines(option, style, marker), label));
         }
         Text::from(lines)
     }
-
-    fn user_input(&self) -> Option<String> {
-        self.user_input.clone()
-    }
-
-    fn answer(&self) -> String {
-        self.answer.clone()
-    }
-
-    fn question(&self) -> String {
-        self.question.clone()
-    }
-
-    fn score(&self) -> u16 {
-        self.score
-    }
-
-    fn answered(&self) -> bool {
-        self.user_input.is_some()
-    }
 }
 
-impl Question for MultiSelect {
+impl QuestionText for MultiSelect {
     fn convert_text(&self, state: State, q_index: usize) -> Text<'_> {
-        let mut lines = vec![];
-        let line = self.convert_question(state, q_index);
-        lines.push(line);
+        let mut lines = self.convert_question(state, q_index);
         for (i, option) in self.options.iter().enumerate() {
             let user_input_idx = self.user_input.clone().map(|user_input| {
                 user_input
@@ -364,203 +220,226 @@ impl Question for MultiSelect {
                 .clone()
                 .chars()
                 .filter_map(|c| to_idx(c.to_string().as_str()))
-                .collect();
+                .collect::<Vec<usize>>();
+            let marker = checkbox(
+                user_input_idx
+                    .as_ref()
+                    .is_some_and(|idx: &Vec<usize>| idx.contains(&i)),
+            );
+            let label = self.option_label(state, i, &user_input_idx, &answer_idx);
             let style = self.option_style(state, i, user_input_idx, answer_idx);
-            lines.push(Line::from(Span::styled(format!("  {option}"), style)));
+            lines.extend(labeled(option_lines(option, style, marker), label));
         }
         Text::from(lines)
     }
-
-    fn user_input(&self) -> Option<String> {
-        self.user_input.clone()
-    }
-
-    fn answer(&self) -> String {
-        self.answer.clone()
-    }
-    fn question(&self) -> String {
-        self.question.clone()
-    }
-    fn score(&self) -> u16 {
-        self.score
-    }
-
-    fn answered(&self) -> bool {
-        self.user_input.is_some()
-    }
 }
 
-impl Question for Judge {
+impl QuestionText for Judge {
     fn convert_text(&self, state: State, q_index: usize) -> Text<'_> {
         Text::from(self.convert_question(state, q_index))
     }
+}
 
-    fn user_input(&self) -> Option<String> {
-        self.user_input.clone()
-    }
-
-    fn answer(&self) -> String {
-        self.answer.clone()
-    }
-    fn question(&self) -> String {
-        self.question.clone()
-    }
-    fn score(&self) -> u16 {
-        self.score
-    }
-
-    fn answered(&self) -> bool {
-        self.user_input.is_some()
+impl QuestionText for FillIn {
+    fn convert_text(&self, state: State, q_index: usize) -> Text<'_> {
+        let stem = blank::parse(&self.question);
+        let fills = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let blank = stem
+                    .blanks
+                    .get(i)
+                    .copied()
+                    .unwrap_or_else(|| stem.default_blank());
+                match item.user_input.clone() {
+                    None => empty_blank(blank),
+                    Some(user_input) => {
+                        self.user_input_span(state, user_input, item.answer.clone(), blank)
+                    }
+                }
+            })
+            .collect();
+        Text::from(markup::to_marked_lines(fill_stem(q_index, stem, fills)))
     }
 }
 
-#[derive(Copy, Clone)]
-pub enum Lang {
-    EN,
-    CN,
-}
-impl Lang {
-    fn parentheses(&self) -> (String, String) {
-        match self {
-            Lang::EN => ("(".to_string(), ")".to_string()),
-            Lang::CN => ("（".to_string(), "）".to_string()),
+/// 材料题：材料在前，子题缩进显示在材料下方，并单独编号
+fn group_text(group: &Group, state: State, q_index: usize) -> Text<'_> {
+    let mut lines = markup::to_lines(vec![Span::from(format!(
+        "{}: {}",
+        q_index + 1,
+        group.question
+    ))]);
+    for (i, q) in group.questions.iter().enumerate() {
+        for line in q.convert_text(state, i).lines {
+            let mut spans = vec![Span::raw("    ")];
+            spans.extend(line.spans);
+            lines.push(Line::from(spans));
         }
     }
+    Text::from(lines)
+}
 
-    fn pattern(&self) -> Regex {
-        match self {
-            Lang::EN => Regex::new(r"\(\s*\)|\(\)").unwrap(),
-            Lang::CN => Regex::new(r"（\s*）|（）").unwrap(),
-        }
-    }
+/// 未作答的填空位置
+fn empty_blank(blank: Blank) -> Vec<Span<'static>> {
+    vec![
+        Span::styled(blank.open, *DEFAULT_STYLE),
+        Span::styled(blank.close, *DEFAULT_STYLE),
+    ]
+}
 
-    fn check(question: &str) -> Self {
-        if Lang::CN.pattern().is_match(question) {
-            return Lang::CN;
-        } else if Lang::EN.pattern().is_match(question) {
-            return Lang::EN;
-        }
-        panic!("为能判断中英文括号")
-    }
+/// 把题干片段与各填空位置的内容交替拼接，填空位置不够时多出的内容依次补在末尾，
+/// 内容不够时剩余的填空位置显示为空。填空位置的内容标记为作答，不参与代码高亮
+fn fill_stem(
+    q_index: usize,
+    stem: Stem,
+    fills: Vec<Vec<Span<'static>>>,
+) -> Vec<(Span<'static>, bool)> {
+    let text = |s: String| (Span::styled(s, *DEFAULT_STYLE), false);
+    let fill = |spans: Vec<Span<'static>>| spans.into_iter().map(|span| (span, true));
+    let mut spans = vec![text(format!("{}: ", q_index + 1))];
+    let mut fills = fills.into_iter();
+    let mut parts = stem.parts.into_iter();
+    spans.extend(parts.next().map(text));
+    for (blank, part) in stem.blanks.into_iter().zip(parts) {
+        spans.extend(fill(fills.next().unwrap_or_else(|| empty_blank(blank))));
+        spans.push(text(part));
+    }
+    for spans_of_fill in fills {
+        spans.push(text(" ".to_string()));
+        spans.extend(fill(spans_of_fill));
+    }
+    spans
 }
 
-impl Question for FillIn {
-    fn convert_text(&self, state: State, q_index: usize) -> Text<'_> {
-        let question = self.question.clone();
-        let lang = Lang::check(question.as_str());
-        let vec = lang
-            .pattern()
-            .split(&question)
-            .enumerate()
-            .map(|(i, s)| {
-                if i == 0 {
-                    Span::styled(format!("{}: {}", q_index + 1, s), *DEFAULT_STYLE)
-                } else {
-                    Span::styled(s.to_string(), *DEFAULT_STYLE)
-                }
-            })
-            .collect::<Vec<Span>>();
+/// 选项可能包含多行内容或代码块，续行与首行保持相同缩进
+pub fn option_lines(option: &str, style: Style, marker: &'static str) -> Vec<Line<'static>> {
+    // 选中或正确的选项带有作答样式，代码块中也不做高亮
+    let marked = style != *DEFAULT_STYLE;
+    markup::to_marked_lines(vec![(Span::styled(option.to_string(), style), marked)])
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let prefix = if i == 0 {
+                Span::styled(format!("  {marker} "), style)
+            } else {
+                Span::raw(" ".repeat(marker.len() + 3))
+            };
+            let mut spans = vec![prefix];
+            spans.extend(line.spans);
+            Line::from(spans)
+        })
+        .collect()
+}
 
-        let mut spans = self
-            .items
-            .iter()
-            .map(|item| match item.user_input.clone() {
-                None => vec![
-                    Span::styled(lang.parentheses().0, *DEFAULT_STYLE),
-                    Span::styled(lang.parentheses().1, *DEFAULT_STYLE),
-                ],
-                Some(user_input) => {
-                    self.user_input_span(state, user_input.to_string(), item.answer.clone(), lang)
-                }
-            })
-            .collect::<Vec<Vec<Span>>>();
-        spans.push(vec![Span::default()]);
-        let spans: Vec<Span> = vec
-            .into_iter()
-            .zip(spans)
-            .flat_map(|(s1, s2)| {
-                let mut vec = vec![s1];
-                vec.extend(s2);
-                vec
-            })
-            .collect();
-        Text::from(Line::from(spans))
+/// 在选项的第一行末尾附加文字标记
+fn labeled(mut lines: Vec<Line<'static>>, label: Option<&'static str>) -> Vec<Line<'static>> {
+    if let (Some(label), Some(line)) = (label, lines.first_mut()) {
+        line.spans.push(Span::styled(label, *DEFAULT_STYLE));
     }
+    lines
+}
 
-    fn cal_score(&self) -> u16 {
-        self.items
-            .iter()
-            .map(|item| match item.user_input.clone() {
-                Some(user_input) if user_input == item.answer => item.score,
-                _ => 0,
-            })
-            .sum()
-    }
+/// 作答弹框中输入框的提示
+pub trait InputKindView {
+    fn hint(&self) -> String;
+}
 
-    fn user_input(&self) -> Option<String> {
-        unimplemented!("无需为填空题实现该方法")
+impl InputKindView for InputKind {
+    fn hint(&self) -> String {
+        match self {
+            InputKind::Single(n) => i18n::tf("input.single", &[("range", &letter_range(*n))]),
+            InputKind::Multi(n) => i18n::tf("input.multi", &[("range", &letter_range(*n))]),
+            InputKind::Judge => i18n::t("input.judge").to_string(),
+            InputKind::Text => i18n::t("input.text").to_string(),
+        }
     }
+}
 
-    fn answer(&self) -> String {
-        unimplemented!("无需为填空题实现该方法")
-    }
-    fn question(&self) -> String {
-        self.question.clone()
-    }
-    fn score(&self) -> u16 {
-        unimplemented!("无需为填空题实现该方法")
-    }
-    fn answered(&self) -> bool {
-        self.items.iter().all(|item| item.user_input.is_some())
+/// 作答不合法时给用户的提示，作答弹框、局域网考试与 HTTP 接口共用
+pub fn input_error_text(error: &InputError) -> String {
+    match error {
+        InputError::InvalidOption { option, range } => i18n::tf(
+            "input.invalid_option",
+            &[("option", option), ("range", range)],
+        ),
+        InputError::SingleOnly { range } => i18n::tf("input.single_only", &[("range", range)]),
+        InputError::Judge => i18n::t("input.judge_invalid").to_string(),
+        InputError::TooMany { expected } => i18n::tf("input.too_many", &[("expected", expected)]),
     }
 }
 
-impl FillIn {
-    fn user_input(&self) -> Vec<Option<String>> {
-        self.items
-            .iter()
-            .map(|item| item.user_input.clone())
-            .collect()
-    }
+/// 单选题选项前的单选框
+pub fn radio(selected: bool) -> &'static str {
+    if selected { "(*)" } else { "( )" }
 }
 
-fn to_idx(answer: &str) -> Option<usize> {
-    match answer {
-        "A" | "a" => Some(0),
-        "B" | "b" => Some(1),
-        "C" | "c" => Some(2),
-        "D" | "d" => Some(3),
-        "E" | "e" => Some(4),
-        "F" | "f" => Some(5),
-        "G" | "g" => Some(6),
-        "H" | "h" => Some(7),
-        _ => None,
-    }
+/// 多选题选项前的复选框
+pub fn checkbox(selected: bool) -> &'static str {
+    if selected { "[x]" } else { "[ ]" }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::components::examination::question::{MultiSelect, Question};
-    use regex::Regex;
+    use crate::components::examination::State;
+    use crate::components::examination::question::{QuestionText, QuestionView};
+    use examination::engine::question::{FillIn, FillInItem, Group, Judge, QuestionEnum};
 
     #[test]
-    fn test_cal_score() {
-        let multi_select = MultiSelect {
-            question: "question".to_string(),
-            options: vec!["A".to_string(), "B".to_string()],
-            answer: "AB".to_string(),
-            user_input: Some("ba".to_string()),
+    fn test_blanks() {
+        let judge = Judge {
+            question: "The sun rises in the east (?)".to_string(),
+            answer: "Yes".to_string(),
+            user_input: Some("Yes".to_string()),
+            score: 1,
+            explanation: None,
+            reference: None,
+        };
+        let text = judge.convert_text(State::Ing, 0).to_string();
+        assert_eq!(text, "1: The sun rises in the east (?) (Yes)");
+
+        let item = |answer: &str| FillInItem {
+            answer: answer.to_string(),
+            user_input: Some(answer.to_string()),
             score: 1,
         };
-        let score = multi_select.cal_score();
-        assert_eq!(score, 1);
+        let mut fill_in = FillIn {
+            question: "Rust 1.0 was released in {{blank}}, \\(see notes\\).".to_string(),
+            items: vec![item("2015"), item("May")],
+            explanation: None,
+            reference: None,
+        };
+        let text = fill_in.convert_text(State::Ing, 0).to_string();
+        assert_eq!(
+            text,
+            "1: Rust 1.0 was released in (2015), (see notes). (May)"
+        );
+        let fill_in_enum = QuestionEnum::FillIn(fill_in.clone());
+        assert!(fill_in_enum.check_blanks().is_err());
+
+        fill_in.question = "Released on ___ ___ 2015".to_string();
+        let text = fill_in.convert_text(State::Ing, 0).to_string();
+        assert_eq!(text, "1: Released on (2015) (May) 2015");
+        assert!(QuestionEnum::FillIn(fill_in).check_blanks().is_ok());
     }
 
     #[test]
-    fn test_regex() {
-        let pattern = Regex::new(r"\(\s*\)|\(\)|（\s*）|（）").unwrap();
-        let question = "太阳东升西落，对吗？（ ）";
-        let vec = pattern.split(question).collect::<Vec<&str>>();
-        assert_eq!(vec, vec!["太阳东升西落，对吗？", ""])
+    fn test_explanation_text() {
+        let group = QuestionEnum::Group(Group {
+            question: "阅读材料，回答问题".to_string(),
+            questions: vec![QuestionEnum::Judge(Judge {
+                question: "太阳东升西落，对吗？（ ）".to_string(),
+                answer: "Yes".to_string(),
+                user_input: None,
+                score: 1,
+                explanation: Some("地球自西向东自转".to_string()),
+                reference: None,
+            })],
+            explanation: Some("“双奥之城”指同时举办过夏季和冬季奥运会的城市".to_string()),
+            reference: None,
+        });
+        assert_eq!(group.explanation_text().lines.len(), 3);
     }
 }
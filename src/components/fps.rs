@@ -1,12 +1,14 @@
+#![allow(dead_code)] // Remove this once you start using the code
+
 use std::time::Instant;
 
 use color_eyre::Result;
 use ratatui::{
+    Frame,
     layout::{Constraint, Layout, Rect},
     style::{Style, Stylize},
     text::Span,
     widgets::Paragraph,
-    Frame,
 };
 
 use super::Component;
@@ -0,0 +1,223 @@
+//! 快捷键帮助：按当前模式列出所有绑定，并为各组件生成底部的操作提示。
+
+use crate::action::Action;
+use crate::app::{Mode, ModeHolderLock};
+use crate::components::Component;
+use crate::components::area_util::centered_rect;
+use crate::config::{Config, KeyBindings, key_event_to_string};
+use crate::{i18n, theme};
+use color_eyre::Result;
+use ratatui::Frame;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Constraint::{Length, Min};
+use ratatui::layout::{Alignment, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Widget};
+
+/// 无法通过配置修改的按键及其说明的文字键
+fn fixed_keys(mode: Mode, study: bool) -> &'static [(&'static str, &'static str)] {
+    match mode {
+        Mode::Examination if study => &[("a-h / 1-8", "help.choose_option"), ("0-5", "help.rate")],
+        Mode::Examination => &[("a-h / 1-8", "help.choose_option")],
+        Mode::Input => &[
+            ("a-h / 1-8", "help.toggle_option"),
+            ("left / right / home / end", "help.move_cursor"),
+            ("ctrl-left / ctrl-right", "help.move_word"),
+            ("backspace / delete", "help.delete_char"),
+            ("ctrl-u / ctrl-w", "help.delete_line_word"),
+        ],
+        Mode::Jump => &[("0-9", "help.type_number"), ("backspace", "help.delete")],
+        Mode::Broadcast => &[("text", "help.type_message"), ("backspace", "help.delete")],
+        Mode::Unlock => &[("text", "help.type_password"), ("backspace", "help.delete")],
+        Mode::Alert | Mode::Summary | Mode::Help | Mode::Proctor => &[],
+    }
+}
+
+/// 按键序列的显示文本，例如 `ctrl-s`、`g g`
+fn key_sequence_to_string(keys: &[crossterm::event::KeyEvent]) -> String {
+    keys.iter()
+        .map(key_event_to_string)
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// 该模式下绑定到某个动作的按键，有多个时取最短的一个，保证显示稳定
+pub fn key_hint(keybindings: &KeyBindings, mode: Mode, action: &Action) -> Option<String> {
+    keybindings
+        .get(&mode)?
+        .iter()
+        .filter(|(_, a)| *a == action)
+        .map(|(keys, _)| key_sequence_to_string(keys))
+        .min_by(|a, b| a.len().cmp(&b.len()).then(a.cmp(b)))
+}
+
+/// 由当前绑定生成底部提示，`label` 为提示文字的键，未绑定的动作不显示
+pub fn footer(keybindings: &KeyBindings, mode: Mode, entries: &[(Action, &str)]) -> String {
+    entries
+        .iter()
+        .filter_map(|(action, label)| {
+            key_hint(keybindings, mode, action)
+                .map(|key| i18n::tf("footer.entry", &[("key", &key), ("label", &i18n::t(label))]))
+        })
+        .collect::<Vec<String>>()
+        .join(" | ")
+}
+
+pub struct Help {
+    config: Config,
+    mode_holder: ModeHolderLock,
+    /// 打开帮助前的模式，关闭时恢复
+    previous: Mode,
+    /// 复习模式才有评分按键
+    study: bool,
+}
+
+impl Help {
+    pub fn new(mode_holder: ModeHolderLock, study: bool) -> Self {
+        Self {
+            config: Config::default(),
+            mode_holder,
+            previous: Mode::default(),
+            study,
+        }
+    }
+
+    fn open(&mut self) {
+        self.previous = self.mode_holder.get_mode();
+        self.mode_holder.set_mode(Mode::Help);
+    }
+
+    fn close(&mut self) {
+        self.mode_holder.set_mode(self.previous);
+    }
+
+    /// 帮助面板的内容：先列出可配置的绑定（按动作排序），再列出固定按键
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut bindings = self
+            .config
+            .keybindings
+            .get(&self.previous)
+            .map(|bindings| {
+                bindings
+                    .iter()
+                    .filter_map(|(keys, action)| {
+                        action
+                            .description()
+                            .map(|d| (action.to_string(), key_sequence_to_string(keys), d))
+                    })
+                    .collect::<Vec<(String, String, &str)>>()
+            })
+            .unwrap_or_default();
+        bindings.sort();
+        let key_style = theme::style("popup.key");
+        let mut lines = bindings
+            .into_iter()
+            .map(|(_, key, description)| {
+                Line::from(vec![
+                    Span::styled(format!("{key:>16}  "), key_style),
+                    Span::raw(description),
+                ])
+            })
+            .collect::<Vec<Line>>();
+        for (key, description) in fixed_keys(self.previous, self.study) {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{key:>16}  "), key_style),
+                Span::raw(i18n::t(description)),
+            ]));
+        }
+        lines
+    }
+}
+
+impl Component for Help {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::Help if self.mode_holder.get_mode() == Mode::Help => self.close(),
+            Action::Help => self.open(),
+            Action::CancelDialog if self.mode_holder.get_mode() == Mode::Help => self.close(),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if self.mode_holder.get_mode() == Mode::Help {
+            frame.render_widget(&*self, area);
+        }
+        Ok(())
+    }
+}
+
+impl Widget for &Help {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let area = centered_rect(60, 80, area);
+        Clear.render(area, buf);
+        let block = Block::default()
+            .title(i18n::t("help.title"))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .style(theme::style("popup"));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let [list_area, footer_area] = Layout::vertical([Min(0), Length(1)]).areas(inner);
+        Paragraph::new(self.lines()).render(list_area, buf);
+        Line::raw(footer(
+            &self.config.keybindings,
+            Mode::Help,
+            &[(Action::CancelDialog, "footer.close")],
+        ))
+        .centered()
+        .render(footer_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::action::Action;
+    use crate::app::Mode;
+    use crate::components::help::{fixed_keys, footer, key_hint};
+    use crate::config::KeyBindings;
+
+    #[test]
+    fn test_footer() {
+        let keybindings: KeyBindings = json5::from_str(
+            r#"{"Examination": {"<Down>": "NextQuestion", "<j>": "NextQuestion", "<Ctrl-s>": "Submit"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            key_hint(&keybindings, Mode::Examination, &Action::NextQuestion),
+            Some("j".to_string())
+        );
+        assert_eq!(
+            footer(
+                &keybindings,
+                Mode::Examination,
+                &[
+                    (Action::NextQuestion, "footer.select"),
+                    (Action::Help, "footer.help"),
+                    (Action::Submit, "footer.submit"),
+                ],
+            ),
+            "j 选择 | ctrl-s 交卷"
+        );
+    }
+
+    #[test]
+    fn test_fixed_keys_rate_in_study() {
+        let has_rate = |study| {
+            fixed_keys(Mode::Examination, study)
+                .iter()
+                .any(|(_, d)| *d == "help.rate")
+        };
+        assert!(has_rate(true));
+        assert!(!has_rate(false));
+    }
+}
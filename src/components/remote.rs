@@ -0,0 +1,96 @@
+//! 联网考试：把作答内容与交卷请求同步给考试服务端，并把服务端的消息转换为动作。
+
+use crate::action::{Action, ConfirmEvent};
+use crate::components::Component;
+use crate::i18n;
+use crate::server::client::Connection;
+use crate::server::protocol::{self, ClientMessage, ServerMessage};
+use color_eyre::Result;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use tokio::sync::mpsc::{self, UnboundedSender};
+use tracing::error;
+
+pub struct Remote {
+    /// 建立后台任务前持有的连接
+    connection: Option<Connection>,
+    /// 发往服务端的消息
+    message_tx: Option<UnboundedSender<ClientMessage>>,
+}
+
+impl Remote {
+    pub fn new(connection: Connection) -> Self {
+        Self {
+            connection: Some(connection),
+            message_tx: None,
+        }
+    }
+
+    fn send(&self, message: ClientMessage) {
+        if let Some(tx) = &self.message_tx {
+            let _ = tx.send(message);
+        }
+    }
+}
+
+fn to_action(message: ServerMessage) -> Option<Action> {
+    match message {
+        ServerMessage::Result { score, paper } => Some(Action::Graded(score, paper)),
+        ServerMessage::Deadline(deadline) => Some(Action::Deadline(deadline)),
+        ServerMessage::Broadcast(msg) | ServerMessage::Error(msg) => {
+            Some(Action::Alert(msg, ConfirmEvent::Nothing))
+        }
+        ServerMessage::Token(_) | ServerMessage::Waiting { .. } | ServerMessage::Paper { .. } => {
+            None
+        }
+    }
+}
+
+impl Component for Remote {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        let Some(connection) = self.connection.take() else {
+            return Ok(());
+        };
+        let (mut reader, mut writer) = connection.into_split();
+        let (message_tx, mut message_rx) = mpsc::unbounded_channel::<ClientMessage>();
+        self.message_tx = Some(message_tx);
+        tokio::spawn(async move {
+            while let Some(message) = message_rx.recv().await {
+                if let Err(e) = protocol::send(&mut writer, &message).await {
+                    error!("Failed to send {message:?}: {e}");
+                    break;
+                }
+            }
+        });
+        tokio::spawn(async move {
+            while let Ok(Some(message)) = protocol::recv::<_, ServerMessage>(&mut reader).await {
+                if let Some(action) = to_action(message) {
+                    let _ = tx.send(action);
+                }
+            }
+            let msg = i18n::t("join.disconnected").to_string();
+            let _ = tx.send(Action::Alert(msg, ConfirmEvent::Nothing));
+        });
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::AnswerChanged(question_type, index, user_input) => {
+                self.send(ClientMessage::Answer {
+                    question_type,
+                    index,
+                    user_input,
+                })
+            }
+            Action::RemoteSubmit => self.send(ClientMessage::Submit),
+            Action::Audited(event) => self.send(ClientMessage::Audit(event)),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, _frame: &mut Frame, _area: Rect) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,373 @@
+use crate::action::Action;
+use crate::app::{Mode, ModeHolderLock};
+use crate::components::Component;
+use crate::components::examination::{self, QuestionEnum, QuestionView, State, markup};
+use crate::components::help;
+use crate::config::Config;
+use crate::{i18n, theme};
+use chrono::{Days, Local, NaiveDate};
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Constraint::{Length, Min};
+use ratatui::layout::{Alignment, Layout, Rect};
+use ratatui::text::{Line, Text};
+use ratatui::widgets::{Block, Borders, Paragraph, Widget, Wrap};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tracing::error;
+
+const STUDY_FILE: &str = "study.json";
+
+/// 单道题目的复习记录，按 SM-2 算法调度
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Card {
+    /// 连续答对的次数
+    repetitions: u32,
+    /// 复习间隔（天）
+    interval: u32,
+    /// 难度系数
+    ease: f64,
+    /// 下次复习日期
+    due: NaiveDate,
+    /// 最近一次的回忆评分（0-5）
+    last_quality: Option<u8>,
+    /// 最近一次复习日期
+    last_reviewed: Option<NaiveDate>,
+}
+
+impl Card {
+    fn new(today: NaiveDate) -> Self {
+        Self {
+            repetitions: 0,
+            interval: 0,
+            ease: 2.5,
+            due: today,
+            last_quality: None,
+            last_reviewed: None,
+        }
+    }
+
+    /// 根据回忆评分更新间隔与难度系数，评分低于 3 视为遗忘，从头开始
+    fn review(&mut self, quality: u8, today: NaiveDate) {
+        let quality = quality.min(5);
+        if quality < 3 {
+            self.repetitions = 0;
+            self.interval = 1;
+        } else {
+            self.repetitions += 1;
+            self.interval = match self.repetitions {
+                1 => 1,
+                2 => 6,
+                _ => (self.interval as f64 * self.ease).round() as u32,
+            };
+        }
+        let q = (5 - quality) as f64;
+        self.ease = (self.ease + 0.1 - q * (0.08 + q * 0.02)).max(1.3);
+        self.due = today
+            .checked_add_days(Days::new(self.interval as u64))
+            .unwrap_or(today);
+        self.last_quality = Some(quality);
+        self.last_reviewed = Some(today);
+    }
+
+    fn is_due(&self, today: NaiveDate) -> bool {
+        self.due <= today
+    }
+}
+
+/// 复习进度
+#[derive(Eq, PartialEq, Copy, Clone)]
+enum Phase {
+    /// 正在看题
+    Asking,
+    /// 已显示答案，等待评分；自动判分时附带建议评分
+    Revealed(Option<u8>),
+}
+
+pub struct Study {
+    /// 复习记录，以题目 ID 为键
+    cards: HashMap<String, Card>,
+    /// 今日待复习的题目
+    queue: VecDeque<QuestionEnum>,
+    /// 本次已复习的题目数
+    reviewed: usize,
+    phase: Phase,
+    path: PathBuf,
+    question_tx: UnboundedSender<QuestionEnum>,
+    answer_rx: UnboundedReceiver<QuestionEnum>,
+    mode_holder: ModeHolderLock,
+    config: Config,
+}
+
+impl Study {
+    pub fn new(
+        question_tx: UnboundedSender<QuestionEnum>,
+        answer_rx: UnboundedReceiver<QuestionEnum>,
+        mode_holder: ModeHolderLock,
+        config: Config,
+    ) -> Self {
+        let path = config.config.data_dir.join(STUDY_FILE);
+        let cards = Self::load(&path);
+        let today = Local::now().date_naive();
+        let bank = examination::load_bank(&config)
+            .into_values()
+            .flatten()
+            .collect::<Vec<QuestionEnum>>();
+        let queue = Self::due_questions(bank, &cards, today);
+        Self {
+            cards,
+            queue,
+            reviewed: 0,
+            phase: Phase::Asking,
+            path,
+            question_tx,
+            answer_rx,
+            mode_holder,
+            config,
+        }
+    }
+
+    fn load(path: &PathBuf) -> HashMap<String, Card> {
+        let mut content = String::new();
+        match File::open(path).and_then(|mut f| f.read_to_string(&mut content)) {
+            Ok(_) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                error!("Fail to parse {}: {e}", path.display());
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        }
+    }
+
+    fn save(&self) {
+        let result = serde_json::to_string_pretty(&self.cards)
+            .map_err(std::io::Error::other)
+            .and_then(|content| std::fs::write(&self.path, content));
+        if let Err(e) = result {
+            error!("Fail to save {}: {e}", self.path.display());
+        }
+    }
+
+    /// 到期的题目排在前面（越早到期越靠前），从未复习过的新题排在最后
+    fn due_questions(
+        bank: Vec<QuestionEnum>,
+        cards: &HashMap<String, Card>,
+        today: NaiveDate,
+    ) -> VecDeque<QuestionEnum> {
+        let mut due = bank
+            .into_iter()
+            .filter_map(|q| match cards.get(&q.id()) {
+                Some(card) if card.is_due(today) => Some((Some(card.due), q)),
+                Some(_) => None,
+                None => Some((None, q)),
+            })
+            .collect::<Vec<(Option<NaiveDate>, QuestionEnum)>>();
+        due.sort_by_key(|(due, _)| due.unwrap_or(NaiveDate::MAX));
+        due.into_iter().map(|(_, q)| q).collect()
+    }
+
+    /// 记录评分并进入下一题
+    fn rate(&mut self, quality: u8) {
+        let Some(q) = self.queue.pop_front() else {
+            return;
+        };
+        let today = Local::now().date_naive();
+        self.cards
+            .entry(q.id())
+            .or_insert_with(|| Card::new(today))
+            .review(quality, today);
+        self.save();
+        // 遗忘的题目当天再复习一次
+        if quality < 3 {
+            let mut q = q;
+            q.set_user_input(vec![None; q.input_size()]);
+            self.queue.push_back(q);
+        }
+        self.reviewed += 1;
+        self.phase = Phase::Asking;
+    }
+
+    /// 自动判分：满分记 5 分，部分得分记 3 分，否则记 1 分
+    fn auto_quality(q: &QuestionEnum) -> u8 {
+        let score = q.cal_score();
+        if score >= q.full_score() {
+            5
+        } else if score > 0 {
+            3
+        } else {
+            1
+        }
+    }
+
+    fn render_question(&self, q: &QuestionEnum, area: Rect, buf: &mut Buffer) {
+        let state = match self.phase {
+            Phase::Asking => State::Ing,
+            Phase::Revealed(_) => State::End,
+        };
+        let mut text = q.convert_text(state, self.reviewed);
+        if let Phase::Revealed(suggested) = self.phase {
+            text.lines.push(Line::default());
+            text.lines.push(Line::styled(
+                i18n::tf("study.answer", &[("answer", &q.answer_text())]),
+                theme::style("question.right"),
+            ));
+            if let Some(quality) = suggested {
+                text.lines.push(Line::from(i18n::tf(
+                    "study.suggested",
+                    &[("quality", &quality)],
+                )));
+            }
+            text.lines.extend(q.explanation_text().lines);
+        }
+        let text = markup::wrap(text, area.width as usize);
+        Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+}
+
+impl Component for Study {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.mode_holder.get_mode() != Mode::Examination || self.queue.is_empty() {
+            return Ok(None);
+        }
+        if let (Phase::Revealed(_), KeyCode::Char(c @ '0'..='5')) = (self.phase, key.code) {
+            self.rate(c as u8 - b'0');
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if self.mode_holder.get_mode() != Mode::Examination || self.queue.is_empty() {
+            return Ok(None);
+        }
+        match (self.phase, action) {
+            (Phase::Asking, Action::OpenAnswer) => {
+                // 弹框请用户作答，提交后自动判分
+                self.question_tx.send(self.queue[0].clone())?;
+            }
+            (Phase::Asking, Action::RevealAnswer) => self.phase = Phase::Revealed(None),
+            (Phase::Revealed(Some(quality)), Action::OpenAnswer) => self.rate(quality),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        frame.render_widget(&mut *self, area);
+        Ok(())
+    }
+}
+
+impl Widget for &mut Study {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        if let Ok(q) = self.answer_rx.try_recv() {
+            self.mode_holder.set_mode(Mode::Examination);
+            if q.user_input().iter().any(Option::is_some) {
+                self.phase = Phase::Revealed(Some(Study::auto_quality(&q)));
+            }
+            if let Some(current) = self.queue.front_mut() {
+                *current = q;
+            }
+        }
+
+        let [title_area, inner_area, footer_area] =
+            Layout::vertical([Length(1), Min(0), Length(1)]).areas(area);
+        Paragraph::new(i18n::tf(
+            "study.title",
+            &[
+                ("reviewed", &self.reviewed),
+                ("remaining", &self.queue.len()),
+            ],
+        ))
+        .style(theme::style("title"))
+        .alignment(Alignment::Center)
+        .render(title_area, buf);
+
+        let block = Block::default().borders(Borders::TOP);
+        let question_area = block.inner(inner_area);
+        block.render(inner_area, buf);
+        let keybindings = &self.config.keybindings;
+        let footer = match (self.queue.front(), self.phase) {
+            (None, _) => {
+                Paragraph::new(Text::from(i18n::t("study.done")))
+                    .alignment(Alignment::Center)
+                    .render(question_area, buf);
+                help::footer(
+                    keybindings,
+                    Mode::Examination,
+                    &[(Action::Quit, "footer.quit")],
+                )
+            }
+            (Some(q), Phase::Asking) => {
+                self.render_question(q, question_area, buf);
+                help::footer(
+                    keybindings,
+                    Mode::Examination,
+                    &[
+                        (Action::OpenAnswer, "footer.write_answer"),
+                        (Action::RevealAnswer, "footer.show_answer"),
+                        (Action::Help, "footer.help"),
+                        (Action::Quit, "footer.quit"),
+                    ],
+                )
+            }
+            (Some(q), Phase::Revealed(suggested)) => {
+                self.render_question(q, question_area, buf);
+                let footer = help::footer(
+                    keybindings,
+                    Mode::Examination,
+                    &[(Action::Help, "footer.help"), (Action::Quit, "footer.quit")],
+                );
+                match suggested {
+                    Some(_) => {
+                        let accept = help::footer(
+                            keybindings,
+                            Mode::Examination,
+                            &[(Action::OpenAnswer, "footer.accept")],
+                        );
+                        format!("{accept} | {} | {footer}", i18n::t("footer.rate"))
+                    }
+                    None => format!("{} | {footer}", i18n::t("footer.rate_hint")),
+                }
+            }
+        };
+        Line::raw(footer).centered().render(footer_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::components::study::Card;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_card_review() {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let mut card = Card::new(today);
+        card.review(5, today);
+        assert_eq!(card.interval, 1);
+        card.review(5, today);
+        assert_eq!(card.interval, 6);
+        card.review(4, today);
+        assert_eq!(card.interval, 16);
+        assert_eq!(card.due, NaiveDate::from_ymd_opt(2025, 1, 17).unwrap());
+        card.review(1, today);
+        assert_eq!(card.repetitions, 0);
+        assert_eq!(card.interval, 1);
+        assert!(card.ease >= 1.3);
+    }
+}
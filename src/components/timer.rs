@@ -1,11 +1,12 @@
+use crate::action::Action;
 use crate::components::Component;
+use crate::{i18n, theme};
 use chrono::{DateTime, Local};
+use ratatui::Frame;
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Layout, Rect};
-use ratatui::prelude::{Span, Style};
-use ratatui::style::{Modifier, Stylize};
+use ratatui::prelude::Span;
 use ratatui::widgets::{Paragraph, Widget};
-use ratatui::Frame;
 use std::time::Duration;
 
 pub struct Timer {
@@ -20,9 +21,25 @@ impl Timer {
             duration: Duration::from_secs(seconds),
         }
     }
+
+    /// 倒计时到指定时刻，用于由考试服务端统一规定的截止时间
+    pub fn until(deadline: DateTime<Local>) -> Timer {
+        let start_time = Local::now();
+        Timer {
+            start_time,
+            duration: (deadline - start_time).to_std().unwrap_or_default(),
+        }
+    }
 }
 
 impl Component for Timer {
+    fn update(&mut self, action: Action) -> color_eyre::Result<Option<Action>> {
+        if let Action::Deadline(deadline) = action {
+            *self = Timer::until(deadline);
+        }
+        Ok(None)
+    }
+
     fn draw(&mut self, frame: &mut Frame, area: Rect) -> color_eyre::Result<()> {
         frame.render_widget(self, area);
         Ok(())
@@ -37,21 +54,17 @@ impl Widget for &mut Timer {
         let now = Local::now();
         let elapsed = now - self.start_time;
         let elapsed_secs = elapsed.num_seconds() as u64;
-        let remaining_secs = self.duration.as_secs() - elapsed_secs;
+        let remaining_secs = self.duration.as_secs().saturating_sub(elapsed_secs);
         let remaining = Duration::from_secs(remaining_secs);
-        let remaining_str = format!(
-            "剩余时间：{:02}:{:02}:{:02}",
+        let time = format!(
+            "{:02}:{:02}:{:02}",
             remaining.as_secs() / 3600,
             remaining.as_secs() / 60,
             remaining.as_secs() % 60
         );
+        let remaining_str = i18n::tf("timer.remaining", &[("time", &time)]);
         let [top, _] = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
-        let span = Span::styled(
-            remaining_str,
-            Style::new()
-                .yellow()
-                .add_modifier(Modifier::BOLD | Modifier::SLOW_BLINK),
-        );
+        let span = Span::styled(remaining_str, theme::style("timer"));
         let paragraph = Paragraph::new(span).right_aligned();
         paragraph.render(top, buf);
     }
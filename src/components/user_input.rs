@@ -2,14 +2,24 @@ use crate::action::Action;
 use crate::app::{Mode, ModeHolderLock};
 use crate::components::Component;
 use crate::components::area_util::centered_rect;
-use crate::components::examination::QuestionEnum;
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::components::examination::{
+    InputKindView, QuestionEnum, checkbox, input_error_text, option_index, option_lines, radio,
+    to_letter,
+};
+use crate::components::help;
+use crate::config::{Config, KeyBindings};
+use crate::{i18n, theme};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use examination::engine::InvalidInput;
 use ratatui::Frame;
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Position, Rect};
-use ratatui::style::{Color, Style};
-use ratatui::widgets::{Block, Borders, Clear, Paragraph, Widget};
+use ratatui::style::Style;
+use ratatui::widgets::{
+    Block, Borders, Clear, List, ListItem, ListState, Paragraph, StatefulWidget, Widget,
+};
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use unicode_width::UnicodeWidthStr;
 
 pub struct UserInput {
     /// Current value of the input box
@@ -30,6 +40,25 @@ pub struct UserInput {
     input_type: InputType,
     /// 输入框光标位置
     cursor_position: Option<Position>,
+    /// 选择题弹框中当前高亮的选项
+    choice_idx: usize,
+    /// 输入校验失败的提示
+    error: Option<String>,
+    /// 快捷键配置，用于生成弹框中的操作提示
+    keybindings: KeyBindings,
+    /// 上次渲染时可点击的区域：判断题为“是/否”按钮，选择题为各选项，填空题为各输入框
+    click_areas: Vec<Rect>,
+    /// 正在逐个作答子题的材料题
+    group: Option<GroupInput>,
+}
+
+/// 材料题的子题依次在各自题型的弹框中作答，全部确认后一并提交
+struct GroupInput {
+    question: QuestionEnum,
+    /// 正在作答的子题序号
+    child: usize,
+    /// 已确认的子题作答，按输入框顺序排列
+    input: Vec<Option<String>>,
 }
 
 #[derive(Default)]
@@ -37,6 +66,8 @@ enum InputType {
     #[default]
     Fill,
     Judge,
+    /// 单选、多选题使用单选框/复选框作答
+    Choice,
 }
 
 impl Widget for &mut UserInput {
@@ -47,16 +78,7 @@ impl Widget for &mut UserInput {
         match self.mode_holder.get_mode() {
             Mode::Examination => {
                 if let Ok(q) = self.question_rx.try_recv() {
-                    match q {
-                        QuestionEnum::SingleSelect(_) => self.input_type = InputType::Fill,
-                        QuestionEnum::MultiSelect(_) => self.input_type = InputType::Fill,
-                        QuestionEnum::Judge(_) => self.input_type = InputType::Judge,
-                        QuestionEnum::FillIn(_) => self.input_type = InputType::Fill,
-                    }
-                    self.input = q.user_input();
-                    self.current_input_idx = Some(0);
-                    self.question = Some(q);
-                    self.mode_holder.set_mode(Mode::Input);
+                    self.open(q);
                 }
             }
 
@@ -67,6 +89,9 @@ impl Widget for &mut UserInput {
                 InputType::Judge => {
                     self.draw_judge(area, buf);
                 }
+                InputType::Choice => {
+                    self.draw_choice(area, buf);
+                }
             },
             _ => {}
         }
@@ -77,25 +102,37 @@ impl Component for UserInput {
     fn handle_key_event(&mut self, key: KeyEvent) -> color_eyre::Result<Option<Action>> {
         if self.mode_holder.get_mode() == Mode::Input {
             match self.input_type {
+                InputType::Fill if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    match key.code {
+                        KeyCode::Left => self.move_cursor_word_left(),
+                        KeyCode::Right => self.move_cursor_word_right(),
+                        KeyCode::Char('u') => self.delete_to_start(),
+                        KeyCode::Char('w') => self.delete_word(),
+                        KeyCode::Char('a') => self.character_index = 0,
+                        KeyCode::Char('e') => self.move_cursor_end(),
+                        _ => {}
+                    }
+                }
                 InputType::Fill => match key.code {
-                    KeyCode::Tab => self.move_cursor_next(),
-                    KeyCode::Enter => self.submit_message(),
                     KeyCode::Char(to_insert) => self.enter_char(to_insert),
                     KeyCode::Backspace => self.delete_char(),
+                    KeyCode::Delete => self.delete_char_forward(),
                     KeyCode::Left => self.move_cursor_left(),
                     KeyCode::Right => self.move_cursor_right(),
-                    KeyCode::Esc => self.close(),
+                    KeyCode::Home => self.character_index = 0,
+                    KeyCode::End => self.move_cursor_end(),
                     _ => {}
                 },
-                InputType::Judge => match key.code {
-                    KeyCode::Esc => self.close(),
-                    KeyCode::Char('y') | KeyCode::Char('Y') => {
-                        self.input = vec![Some("Yes".to_string())];
-                        self.submit_message()
-                    }
-                    KeyCode::Char('n') | KeyCode::Char('N') => {
-                        self.input = vec![Some("No".to_string())];
-                        self.submit_message()
+                InputType::Judge => {}
+                // 已绑定到其它动作的按键不用于选择选项
+                InputType::Choice => match key.code {
+                    KeyCode::Char(c)
+                        if !key.modifiers.contains(KeyModifiers::CONTROL)
+                            && !self.keybindings.is_bound(Mode::Input, key) =>
+                    {
+                        if let Some(idx) = option_index(c) {
+                            self.toggle_option(idx)
+                        }
                     }
                     _ => {}
                 },
@@ -104,9 +141,71 @@ impl Component for UserInput {
         Ok(None)
     }
 
+    fn register_config_handler(&mut self, config: Config) -> color_eyre::Result<()> {
+        self.keybindings = config.keybindings;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> color_eyre::Result<Option<Action>> {
+        // 弹框可能已被关闭，不论当前模式都清空状态
+        if action == Action::DiscardInput {
+            self.reset();
+            return Ok(None);
+        }
+        if self.mode_holder.get_mode() != Mode::Input {
+            return Ok(None);
+        }
+        match (&self.input_type, action) {
+            (_, Action::CancelDialog) => self.close(),
+            (InputType::Fill | InputType::Choice, Action::ConfirmDialog) => self.submit_message(),
+            (InputType::Fill, Action::NextInput) => self.move_cursor_next(),
+            (InputType::Judge, Action::AnswerYes) => self.answer_judge("Yes"),
+            (InputType::Judge, Action::AnswerNo) => self.answer_judge("No"),
+            (InputType::Choice, Action::NextOption) => self.next_option(),
+            (InputType::Choice, Action::PrevOption) => {
+                self.choice_idx = self.choice_idx.saturating_sub(1)
+            }
+            (InputType::Choice, Action::ToggleOption) => self.toggle_option(self.choice_idx),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn handle_paste_event(&mut self, text: String) -> color_eyre::Result<Option<Action>> {
+        if self.mode_holder.get_mode() == Mode::Input && matches!(self.input_type, InputType::Fill)
+        {
+            self.insert_str(&text);
+        }
+        Ok(None)
+    }
+
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> color_eyre::Result<Option<Action>> {
+        if self.mode_holder.get_mode() != Mode::Input {
+            return Ok(None);
+        }
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let position = Position::new(mouse.column, mouse.row);
+                if let Some(idx) = self.click_areas.iter().position(|a| a.contains(position)) {
+                    self.click(idx);
+                }
+            }
+            MouseEventKind::ScrollDown if matches!(self.input_type, InputType::Choice) => {
+                self.next_option()
+            }
+            MouseEventKind::ScrollUp if matches!(self.input_type, InputType::Choice) => {
+                self.choice_idx = self.choice_idx.saturating_sub(1)
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
     fn draw(&mut self, frame: &mut Frame, area: Rect) -> color_eyre::Result<()> {
         frame.render_widget(&mut *self, area);
-        if let Some(position) = self.cursor_position {
+        if let Some(position) = self.cursor_position
+            && self.mode_holder.get_mode() == Mode::Input
+        {
             frame.set_cursor_position(position)
         }
         Ok(())
@@ -129,6 +228,107 @@ impl UserInput {
             mode_holder: state_holder,
             input_type: InputType::default(),
             cursor_position: None,
+            choice_idx: 0,
+            error: None,
+            click_areas: vec![],
+            keybindings: KeyBindings::default(),
+            group: None,
+        }
+    }
+
+    /// 打开作答弹框，材料题从第一道子题开始作答
+    fn open(&mut self, q: QuestionEnum) {
+        // 没有输入框的题目无需作答
+        if q.input_size() == 0 {
+            self.answer_tx.send(q).unwrap();
+            return;
+        }
+        let question = match &q {
+            QuestionEnum::Group(group) => {
+                let first = group.questions.iter().find(|q| q.input_size() > 0).cloned();
+                let child = group
+                    .questions
+                    .iter()
+                    .take_while(|q| q.input_size() == 0)
+                    .count();
+                self.group = Some(GroupInput {
+                    question: q,
+                    child,
+                    input: vec![],
+                });
+                first.unwrap()
+            }
+            _ => {
+                self.group = None;
+                q
+            }
+        };
+        self.load(question);
+        self.mode_holder.set_mode(Mode::Input);
+    }
+
+    /// 按题型选择弹框并带入已有的作答
+    fn load(&mut self, q: QuestionEnum) {
+        self.input_type = match q {
+            QuestionEnum::SingleSelect(_) | QuestionEnum::MultiSelect(_) => InputType::Choice,
+            QuestionEnum::Judge(_) => InputType::Judge,
+            QuestionEnum::FillIn(_) | QuestionEnum::Group(_) => InputType::Fill,
+        };
+        self.input = q.user_input();
+        self.current_input_idx = Some(0);
+        self.choice_idx = 0;
+        self.error = None;
+        self.click_areas.clear();
+        self.reset_cursor();
+        self.question = Some(q);
+    }
+
+    /// 材料题的下一道子题，没有则返回 None
+    fn next_child(&self) -> Option<(usize, QuestionEnum)> {
+        let group = self.group.as_ref()?;
+        let QuestionEnum::Group(q) = &group.question else {
+            return None;
+        };
+        q.questions
+            .iter()
+            .enumerate()
+            .skip(group.child + 1)
+            .find(|(_, q)| q.input_size() > 0)
+            .map(|(i, q)| (i, q.clone()))
+    }
+
+    /// 点击第 idx 个可点击区域
+    fn click(&mut self, idx: usize) {
+        match self.input_type {
+            InputType::Judge => self.answer_judge(if idx == 0 { "Yes" } else { "No" }),
+            InputType::Choice => self.toggle_option(idx),
+            InputType::Fill => {
+                if self.current_input_idx != Some(idx) {
+                    self.current_input_idx = Some(idx);
+                    self.reset_cursor();
+                }
+                self.character_index = self.current_input().chars().count();
+            }
+        }
+    }
+
+    fn answer_judge(&mut self, answer: &str) {
+        self.input = vec![Some(answer.to_string())];
+        self.submit_message()
+    }
+
+    fn next_option(&mut self) {
+        let size = self.question.as_ref().map_or(0, |q| q.options().len());
+        self.choice_idx = (self.choice_idx + 1).min(size.saturating_sub(1));
+    }
+
+    /// 切换选项，超出选项范围的输入会被忽略
+    fn toggle_option(&mut self, idx: usize) {
+        if let Some(q) = self.question.as_mut()
+            && q.toggle_option(idx)
+        {
+            self.choice_idx = idx;
+            self.input = q.user_input();
         }
     }
 
@@ -143,6 +343,7 @@ impl UserInput {
     }
 
     fn enter_char(&mut self, new_char: char) {
+        self.error = None;
         let index = self.byte_index();
         let mut input = self.current_input();
         input.insert(index, new_char);
@@ -150,6 +351,95 @@ impl UserInput {
         self.move_cursor_right();
     }
 
+    /// 在光标处插入粘贴的文本，换行与制表符替换为空格，其余控制字符丢弃
+    fn insert_str(&mut self, text: &str) {
+        let text = text
+            .trim_end_matches(['\r', '\n'])
+            .chars()
+            .filter_map(|c| match c {
+                '\r' => None,
+                '\n' | '\t' => Some(' '),
+                c if c.is_control() => None,
+                c => Some(c),
+            })
+            .collect::<String>();
+        if text.is_empty() {
+            return;
+        }
+        self.error = None;
+        let index = self.byte_index();
+        let mut input = self.current_input();
+        input.insert_str(index, &text);
+        self.set_current_input(input);
+        self.character_index = self.clamp_cursor(self.character_index + text.chars().count());
+    }
+
+    fn move_cursor_end(&mut self) {
+        self.character_index = self.current_input().chars().count();
+    }
+
+    /// 光标左侧上一个单词的起始位置，先跳过空白再跳过非空白字符
+    fn prev_word_index(&self) -> usize {
+        let chars = self.current_input().chars().collect::<Vec<char>>();
+        let mut idx = self.character_index.min(chars.len());
+        while idx > 0 && chars[idx - 1].is_whitespace() {
+            idx -= 1;
+        }
+        while idx > 0 && !chars[idx - 1].is_whitespace() {
+            idx -= 1;
+        }
+        idx
+    }
+
+    /// 光标右侧下一个单词的结束位置
+    fn next_word_index(&self) -> usize {
+        let chars = self.current_input().chars().collect::<Vec<char>>();
+        let mut idx = self.character_index.min(chars.len());
+        while idx < chars.len() && chars[idx].is_whitespace() {
+            idx += 1;
+        }
+        while idx < chars.len() && !chars[idx].is_whitespace() {
+            idx += 1;
+        }
+        idx
+    }
+
+    fn move_cursor_word_left(&mut self) {
+        self.character_index = self.prev_word_index();
+    }
+
+    fn move_cursor_word_right(&mut self) {
+        self.character_index = self.next_word_index();
+    }
+
+    /// 删除字符下标 [start, end) 范围内的字符，并把光标移到 start
+    fn delete_range(&mut self, start: usize, end: usize) {
+        if start >= end {
+            return;
+        }
+        self.error = None;
+        let input = self.current_input();
+        let input = input
+            .chars()
+            .take(start)
+            .chain(input.chars().skip(end))
+            .collect();
+        self.set_current_input(input);
+        self.character_index = start;
+    }
+
+    fn delete_char_forward(&mut self) {
+        self.delete_range(self.character_index, self.character_index + 1);
+    }
+
+    fn delete_to_start(&mut self) {
+        self.delete_range(0, self.character_index);
+    }
+
+    fn delete_word(&mut self) {
+        self.delete_range(self.prev_word_index(), self.character_index);
+    }
+
     /// Returns the byte index based on the character position.
     ///
     /// Since each character in a string can be contain multiple bytes, it's necessary to calculate
@@ -163,6 +453,7 @@ impl UserInput {
     }
 
     fn delete_char(&mut self) {
+        self.error = None;
         let is_not_cursor_leftmost = self.character_index != 0;
         if is_not_cursor_leftmost {
             // Method "remove" is not used on the saved text for deleting the selected char.
@@ -225,33 +516,108 @@ impl UserInput {
         self.reset_cursor();
     }
 
+    /// 按题型校验并规范化所有输入框，校验失败时定位到出错的输入框并提示
+    fn validate(&mut self) -> bool {
+        match self.question.as_ref().unwrap().normalize_input(&self.input) {
+            Ok(input) => {
+                self.input = input;
+                self.error = None;
+                true
+            }
+            Err(InvalidInput { index, error }) => {
+                self.error = Some(input_error_text(&error));
+                self.current_input_idx = Some(index.min(self.input.len().saturating_sub(1)));
+                self.reset_cursor();
+                self.character_index = self.current_input().chars().count();
+                false
+            }
+        }
+    }
+
     fn submit_message(&mut self) {
+        if !self.validate() {
+            return;
+        }
         let mut question = self.question.take().unwrap();
         question.set_user_input(self.input.clone());
+        if self.group.is_some() {
+            let next = self.next_child();
+            let group = self.group.as_mut().unwrap();
+            group.input.extend(question.user_input());
+            if let Some((child, next)) = next {
+                group.child = child;
+                self.load(next);
+                return;
+            }
+            let mut group = self.group.take().unwrap();
+            group.question.set_user_input(group.input);
+            question = group.question;
+        }
         self.answer_tx.send(question).unwrap();
         self.reset()
     }
 
     fn reset(&mut self) {
         self.input.clear();
+        self.click_areas.clear();
+        self.error = None;
         self.question.take();
+        self.group = None;
         self.reset_cursor();
     }
 
+    /// 放弃本次输入，材料题保留已确认的子题作答
     fn close(&mut self) {
-        self.answer_tx.send(self.question.take().unwrap()).unwrap();
+        let question = match self.group.take() {
+            Some(GroupInput {
+                mut question,
+                child,
+                mut input,
+            }) => {
+                if let QuestionEnum::Group(group) = &question {
+                    input.extend(
+                        group.questions[child..]
+                            .iter()
+                            .flat_map(QuestionEnum::user_input),
+                    );
+                }
+                question.set_user_input(input);
+                question
+            }
+            None => self.question.take().unwrap(),
+        };
+        self.answer_tx.send(question).unwrap();
         self.reset()
     }
 
-    fn draw_judge(&self, area: Rect, buf: &mut Buffer) {
+    /// 弹框标题中的操作提示，由当前的快捷键绑定生成，材料题附上子题进度
+    fn hint(&self, entries: &[(Action, &str)]) -> String {
+        let hint = help::footer(&self.keybindings, Mode::Input, entries);
+        match &self.group {
+            Some(GroupInput {
+                question: QuestionEnum::Group(group),
+                child,
+                ..
+            }) => format!("{}/{} | {hint}", child + 1, group.questions.len()),
+            _ => hint,
+        }
+    }
+
+    fn draw_judge(&mut self, area: Rect, buf: &mut Buffer) {
         let area = centered_rect(50, 20, area);
         Clear.render(area, buf);
+        let key =
+            |action| help::key_hint(&self.keybindings, Mode::Input, &action).unwrap_or_default();
         Block::default()
             .borders(Borders::ALL)
-            .title("Esc to exist, Y to answer Yes, N to answer No.")
+            .title(self.hint(&[
+                (Action::AnswerYes, "footer.answer_yes"),
+                (Action::AnswerNo, "footer.answer_no"),
+                (Action::CancelDialog, "footer.exit"),
+            ]))
             .title_alignment(Alignment::Center)
             .borders(Borders::ALL)
-            .style(Style::default().fg(Color::Gray).bg(Color::DarkGray))
+            .style(theme::style("popup"))
             .render(area, buf);
         let [_, yes_area, no_area, _] = Layout::horizontal([
             Constraint::Percentage(25),
@@ -272,7 +638,7 @@ impl UserInput {
             Constraint::Fill(1),
         ])
         .areas(no_area);
-        Paragraph::new("Yes(Y)")
+        Paragraph::new(i18n::tf("judge.yes", &[("key", &key(Action::AnswerYes))]))
             .style(Style::default())
             .alignment(Alignment::Center)
             .block(
@@ -281,7 +647,7 @@ impl UserInput {
                     .borders(Borders::ALL),
             )
             .render(yes_area, buf);
-        Paragraph::new("No(N)")
+        Paragraph::new(i18n::tf("judge.no", &[("key", &key(Action::AnswerNo))]))
             .style(Style::default())
             .alignment(Alignment::Center)
             .block(
@@ -290,12 +656,88 @@ impl UserInput {
                     .borders(Borders::ALL),
             )
             .render(no_area, buf);
+        self.click_areas = vec![yes_area, no_area];
+    }
+
+    fn draw_choice(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(q) = self.question.as_ref() else {
+            return;
+        };
+        let selected = q.user_input()[0].clone().unwrap_or_default().to_uppercase();
+        let multi = matches!(q, QuestionEnum::MultiSelect(_));
+        let items = q
+            .options()
+            .iter()
+            .enumerate()
+            .map(|(i, option)| {
+                let checked = to_letter(i).is_some_and(|c| selected.contains(c));
+                let marker = if multi {
+                    checkbox(checked)
+                } else {
+                    radio(checked)
+                };
+                ListItem::new(option_lines(option, Style::default(), marker))
+            })
+            .collect::<Vec<ListItem>>();
+        let heights = items.iter().map(ListItem::height).collect::<Vec<usize>>();
+        let height = heights.iter().sum::<usize>() as u16 + 2;
+
+        let area = centered_rect(50, 100, area);
+        let [_, area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height.max(area.height / 5)),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        Clear.render(area, buf);
+        let title = format!(
+            "{} | {}",
+            i18n::t(if multi {
+                "footer.toggle_option"
+            } else {
+                "footer.choose_option"
+            }),
+            self.hint(&[
+                (Action::ConfirmDialog, "footer.submit_answer"),
+                (Action::CancelDialog, "footer.exit"),
+            ])
+        );
+        let block = Block::default()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .style(theme::style("popup"));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let mut state = ListState::default().with_selected(Some(self.choice_idx));
+        StatefulWidget::render(
+            List::new(items)
+                .highlight_style(theme::style("highlight"))
+                .highlight_symbol("> "),
+            inner,
+            buf,
+            &mut state,
+        );
+
+        self.click_areas.clear();
+        let mut y = inner.y;
+        for height in heights.iter().skip(state.offset()) {
+            let height = (*height as u16).min(inner.bottom().saturating_sub(y));
+            self.click_areas
+                .push(Rect::new(inner.x, y, inner.width, height));
+            y += height;
+        }
+        // 滚动隐藏的选项占位，保持下标与选项一致
+        for _ in 0..state.offset() {
+            self.click_areas.insert(0, Rect::default());
+        }
     }
 
     fn draw_fill(&mut self, area: Rect, buf: &mut Buffer) {
         let input_size = self.question.clone().unwrap().input_size();
+        let kinds = self.question.as_ref().unwrap().input_kinds();
         let area = centered_rect(50, 100, area);
-        let high = Self::cal_high(input_size, area);
+        let high = Self::cal_high(input_size, area) + 1;
         let [_, area, _] = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -307,24 +749,39 @@ impl UserInput {
         Clear.render(area, buf);
 
         let title = if input_size == 1 {
-            "Esc to exist, Enter to submit answer."
+            self.hint(&[
+                (Action::ConfirmDialog, "footer.submit_answer"),
+                (Action::CancelDialog, "footer.exit"),
+            ])
         } else {
-            "Esc to exist, Tab to switch, Enter to submit answer."
+            self.hint(&[
+                (Action::NextInput, "footer.switch"),
+                (Action::ConfirmDialog, "footer.submit_answer"),
+                (Action::CancelDialog, "footer.exit"),
+            ])
         };
         Block::default()
             .title(title)
             .title_alignment(Alignment::Center)
             .borders(Borders::ALL)
-            .style(Style::default().fg(Color::Gray).bg(Color::DarkGray))
+            .style(theme::style("popup"))
             .render(area, buf);
-        let [_, area, _] = Layout::default()
+        let [_, area, _, error_area, _] = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Fill(1),
                 Constraint::Length((input_size * 3) as u16),
                 Constraint::Fill(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
             ])
             .areas(area);
+        if let Some(error) = &self.error {
+            Paragraph::new(error.as_str())
+                .style(theme::style("error"))
+                .alignment(Alignment::Center)
+                .render(error_area, buf);
+        }
         let constraints = (0..input_size)
             .map(|_| Constraint::Length(3))
             .collect::<Vec<Constraint>>();
@@ -333,13 +790,19 @@ impl UserInput {
         let mut input_areas = vec![];
         for i in 0..rects.len() {
             let area = centered_rect(80, 100, rects[i]);
+            let title = match kinds.get(i) {
+                Some(kind) if input_size > 1 => format!("{}. {}", i + 1, kind.hint()),
+                Some(kind) => kind.hint(),
+                None => String::new(),
+            };
             Paragraph::new(self.input[i].clone().unwrap_or_default())
                 .style(Style::default())
-                .block(Block::default().borders(Borders::ALL))
+                .block(Block::default().borders(Borders::ALL).title(title))
                 .render(area, buf);
             input_areas.push(area);
         }
         self.set_cursor_position(input_areas[self.current_input_idx.unwrap()]);
+        self.click_areas = input_areas;
     }
 
     fn cal_high(input_size: usize, area: Rect) -> u16 {
@@ -352,10 +815,13 @@ impl UserInput {
     }
 
     fn set_cursor_position(&mut self, input_area: Rect) {
+        // 中文等宽字符占两列，光标位置按光标前文本的显示宽度计算
+        let input = self.current_input();
+        let width = input[..self.byte_index()].width() as u16;
         self.cursor_position = Some(Position::new(
             // Draw the cursor at the current position in the input field.
             // This position is can be controlled via the left and right arrow key
-            input_area.x + self.character_index as u16 + 1,
+            (input_area.x + width + 1).min(input_area.right().saturating_sub(2)),
             // Move one line down, from the border to the input line
             input_area.y + 1,
         ))
@@ -364,21 +830,202 @@ impl UserInput {
 
 #[cfg(test)]
 mod test {
-    use crate::app::{ModeHolder, ModeHolderLock};
-    use crate::components::user_input::UserInput;
+    use crate::action::Action;
+    use crate::app::{Mode, ModeHolder, ModeHolderLock};
+    use crate::components::Component;
+    use crate::components::examination::QuestionEnum;
+    use crate::components::user_input::{InputType, UserInput};
+    use crossterm::event::{
+        KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::{Position, Rect};
+    use ratatui::widgets::Widget;
     use std::sync::{Arc, Mutex};
-    use tokio::sync::mpsc;
+    use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+    /// 未连接题目通道的弹框，返回弹框与接收提交答案的一端
+    fn user_input() -> (UserInput, UnboundedReceiver<QuestionEnum>) {
+        let (_, question_rx) = mpsc::unbounded_channel();
+        let (answer_tx, answer_rx) = mpsc::unbounded_channel();
+        let mode_holder = ModeHolderLock(Arc::new(Mutex::new(ModeHolder::default())));
+        (
+            UserInput::new(question_rx, answer_tx, mode_holder),
+            answer_rx,
+        )
+    }
 
     #[test]
     fn test_byte_index() {
-        let (_, question_rx) = mpsc::unbounded_channel();
-        let (answer_tx, _) = mpsc::unbounded_channel();
-        let mut input = UserInput::new(
-            question_rx,
-            answer_tx,
-            ModeHolderLock(Arc::new(Mutex::new(ModeHolder::default()))),
-        );
+        let (mut input, _) = user_input();
         input.current_input_idx = Some(0);
         assert_eq!(input.byte_index(), 0)
     }
+
+    #[test]
+    fn test_validate() {
+        let (mut input, _) = user_input();
+        input.question = Some(
+            serde_json::from_str(
+                r#"{"Group":{"question":"材料","questions":[
+                    {"Judge":{"question":"对吗？（ ）","answer":"Yes","score":1}},
+                    {"SingleSelect":{"question":"选择（ ）","options":["A: 1","B: 2"],"answer":"B","score":1}}
+                ]}}"#,
+            )
+            .unwrap(),
+        );
+        input.current_input_idx = Some(0);
+        input.input = vec![Some("y".to_string()), Some("Z".to_string())];
+        assert!(!input.validate());
+        assert_eq!(input.current_input_idx, Some(1));
+        assert!(input.error.is_some());
+        input.input[1] = Some("b".to_string());
+        assert!(input.validate());
+        assert_eq!(
+            input.input,
+            vec![Some("Yes".to_string()), Some("B".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_group_children() {
+        let (mut input, mut answer_rx) = user_input();
+        let group: QuestionEnum = serde_json::from_str(
+            r#"{"Group":{"question":"材料","questions":[
+                {"Judge":{"question":"对吗？（ ）","answer":"Yes","score":1}},
+                {"SingleSelect":{"question":"选择（ ）","options":["A: 1","B: 2"],"answer":"B","score":1}}
+            ]}}"#,
+        )
+        .unwrap();
+        // 子题依次使用各自题型的弹框
+        input.open(group.clone());
+        assert!(matches!(input.input_type, InputType::Judge));
+        input.answer_judge("Yes");
+        assert!(matches!(input.input_type, InputType::Choice));
+        assert!(answer_rx.try_recv().is_err());
+        input.toggle_option(1);
+        input.submit_message();
+        let answered = answer_rx.try_recv().unwrap();
+        assert_eq!(
+            answered.user_input(),
+            vec![Some("Yes".to_string()), Some("B".to_string())]
+        );
+
+        // 中途放弃时保留已确认的子题
+        input.open(group);
+        input.answer_judge("No");
+        input.close();
+        assert_eq!(
+            answer_rx.try_recv().unwrap().user_input(),
+            vec![Some("No".to_string()), None]
+        );
+    }
+
+    #[test]
+    fn test_discard_group_on_time_out() {
+        let (mut input, mut answer_rx) = user_input();
+        let mode_holder = input.mode_holder.clone();
+        input.open(
+            serde_json::from_str(
+                r#"{"Group":{"question":"材料","questions":[
+                    {"Judge":{"question":"对吗？（ ）","answer":"Yes","score":1}},
+                    {"Judge":{"question":"错吗？（ ）","answer":"No","score":1}}
+                ]}}"#,
+            )
+            .unwrap(),
+        );
+        input.answer_judge("Yes");
+        // 材料题作答到一半时超时，弹框被关闭
+        mode_holder.set_mode(Mode::Examination);
+        input.update(Action::DiscardInput).unwrap();
+        assert!(input.group.is_none() && input.question.is_none());
+
+        // 之后作答的题目不受材料题影响
+        let judge: QuestionEnum = serde_json::from_str(
+            r#"{"Judge":{"question":"太阳东升西落（ ）","answer":"Yes","score":1}}"#,
+        )
+        .unwrap();
+        input.open(judge.clone());
+        input.answer_judge("No");
+        let answered = answer_rx.try_recv().unwrap();
+        assert_eq!(answered.id(), judge.id());
+        assert_eq!(answered.user_input(), vec![Some("No".to_string())]);
+        assert!(answer_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_option_keys_respect_bindings() {
+        let (mut input, _) = user_input();
+        input.keybindings = json5::from_str(r#"{"Input": {"<b>": "NextOption"}}"#).unwrap();
+        input.open(
+            serde_json::from_str(
+                r#"{"MultiSelect":{"question":"选择（ ）","options":["A: 1","B: 2","C: 3"],"answer":"AC","score":1}}"#,
+            )
+            .unwrap(),
+        );
+        for c in ['a', 'b', '3'] {
+            input
+                .handle_key_event(KeyEvent::from(KeyCode::Char(c)))
+                .unwrap();
+        }
+        // b 已绑定到其它动作，不再选择第二个选项
+        assert_eq!(input.input, vec![Some("AC".to_string())]);
+    }
+
+    #[test]
+    fn test_click_option() {
+        let (mut input, _) = user_input();
+        input.open(
+            serde_json::from_str(
+                r#"{"MultiSelect":{"question":"选择（ ）","options":["A: 1","B: 2","C: 3"],"answer":"AC","score":1}}"#,
+            )
+            .unwrap(),
+        );
+        let area = Rect::new(0, 0, 80, 24);
+        let mut buf = Buffer::empty(area);
+        (&mut input).render(area, &mut buf);
+        let click = |input: &mut UserInput, position: Position| {
+            let mouse = MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column: position.x,
+                row: position.y,
+                modifiers: KeyModifiers::NONE,
+            };
+            input.handle_mouse_event(mouse).unwrap();
+        };
+        let (first, third) = (input.click_areas[0], input.click_areas[2]);
+        click(&mut input, Position::new(third.x + 1, third.y));
+        click(&mut input, Position::new(first.x, first.y));
+        assert_eq!(input.input, vec![Some("AC".to_string())]);
+        assert_eq!(input.choice_idx, 0);
+        // 点击选项以外的位置不改变作答
+        click(&mut input, Position::new(0, 0));
+        assert_eq!(input.input, vec![Some("AC".to_string())]);
+    }
+
+    #[test]
+    fn test_edit() {
+        let (mut input, _) = user_input();
+        input.current_input_idx = Some(0);
+        input.insert_str("北京 上海\r\n");
+        assert_eq!(input.current_input(), "北京 上海");
+        assert_eq!(input.character_index, 5);
+        input.set_cursor_position(Rect::new(0, 0, 40, 3));
+        assert_eq!(input.cursor_position, Some(Position::new(10, 1)));
+
+        input.move_cursor_word_left();
+        assert_eq!(input.character_index, 3);
+        input.delete_word();
+        assert_eq!(input.current_input(), "上海");
+        input.move_cursor_end();
+        input.insert_str(" 天津");
+        input.character_index = 0;
+        input.delete_char_forward();
+        assert_eq!(input.current_input(), "海 天津");
+        input.move_cursor_word_right();
+        assert_eq!(input.character_index, 1);
+        input.delete_to_start();
+        assert_eq!(input.current_input(), " 天津");
+        assert_eq!(input.character_index, 0);
+    }
 }
@@ -7,12 +7,17 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use derive_deref::{Deref, DerefMut};
 use lazy_static::lazy_static;
 use ratatui::style::{Color, Modifier, Style};
-use serde::{de::Deserializer, Deserialize};
+use serde::{Deserialize, de::Deserializer};
 use tracing::error;
 
-use crate::{action::Action, app::Mode};
+use crate::{
+    action::Action,
+    app::{Mode, normalize_key},
+};
 
 const CONFIG: &str = include_str!("../.config/config.json5");
+/// vim 风格的快捷键预设，通过配置 `"keymap": "vim"` 启用
+const VIM_KEYMAP: &str = include_str!("../.config/vim.json5");
 
 #[derive(Clone, Debug, Deserialize, Default)]
 pub struct AppConfig {
@@ -20,6 +25,24 @@ pub struct AppConfig {
     pub data_dir: PathBuf,
     #[serde(default)]
     pub config_dir: PathBuf,
+    /// 是否启用鼠标操作
+    #[serde(default)]
+    pub mouse: bool,
+    /// 快捷键预设，目前支持 `vim`
+    #[serde(default)]
+    pub keymap: Option<String>,
+    /// 主题：dark、light、high-contrast、mono
+    #[serde(default)]
+    pub theme: Option<String>,
+    /// 无障碍模式
+    #[serde(default)]
+    pub accessible: bool,
+    /// 界面语言：zh-CN、en，未设置时读取 `LANG`
+    #[serde(default)]
+    pub language: Option<String>,
+    /// 自定义的填空占位符，与 `{{blank}}`、`___` 和空括号一同识别
+    #[serde(default)]
+    pub blank_placeholder: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -51,7 +74,8 @@ impl Config {
         let config_dir = get_config_dir();
         let mut builder = config::Config::builder()
             .set_default("data_dir", data_dir.to_str().unwrap())?
-            .set_default("config_dir", config_dir.to_str().unwrap())?;
+            .set_default("config_dir", config_dir.to_str().unwrap())?
+            .set_default("mouse", true)?;
 
         let config_files = [
             ("config.json5", config::FileFormat::Json5),
@@ -76,14 +100,16 @@ impl Config {
 
         let mut cfg: Self = builder.build()?.try_deserialize()?;
 
-        for (mode, default_bindings) in default_config.keybindings.iter() {
-            let user_bindings = cfg.keybindings.entry(*mode).or_default();
-            for (key, cmd) in default_bindings.iter() {
-                user_bindings
-                    .entry(key.clone())
-                    .or_insert_with(|| cmd.clone());
+        // 用户配置优先，其次为预设，最后为默认快捷键
+        match cfg.config.keymap.as_deref() {
+            Some("vim") => {
+                let preset: Config = json5::from_str(VIM_KEYMAP).unwrap();
+                cfg.keybindings.merge(&preset.keybindings);
             }
+            Some(keymap) => error!("Unknown keymap preset: {keymap}"),
+            None => {}
         }
+        cfg.keybindings.merge(&default_config.keybindings);
         for (mode, default_styles) in default_config.styles.iter() {
             let user_styles = cfg.styles.entry(*mode).or_default();
             for (style_key, style) in default_styles.iter() {
@@ -114,6 +140,24 @@ pub fn get_config_dir() -> PathBuf {
 #[derive(Clone, Debug, Default, Deref, DerefMut)]
 pub struct KeyBindings(pub HashMap<Mode, HashMap<Vec<KeyEvent>, Action>>);
 
+impl KeyBindings {
+    /// 合并另一组快捷键，已存在的绑定保持不变
+    pub fn merge(&mut self, other: &KeyBindings) {
+        for (mode, bindings) in other.iter() {
+            let current = self.entry(*mode).or_default();
+            for (key, action) in bindings.iter() {
+                current.entry(key.clone()).or_insert_with(|| action.clone());
+            }
+        }
+    }
+
+    /// 按键在该模式下是否绑定了动作
+    pub fn is_bound(&self, mode: Mode, key: KeyEvent) -> bool {
+        self.get(&mode)
+            .is_some_and(|bindings| bindings.contains_key(&vec![normalize_key(key)]))
+    }
+}
+
 impl<'de> Deserialize<'de> for KeyBindings {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -286,8 +330,7 @@ pub fn parse_key_sequence(raw: &str) -> Result<Vec<KeyEvent>, String> {
     }
     let raw = if !raw.contains("><") {
         let raw = raw.strip_prefix('<').unwrap_or(raw);
-        let raw = raw.strip_prefix('>').unwrap_or(raw);
-        raw
+        raw.strip_prefix('>').unwrap_or(raw)
     } else {
         raw
     };
@@ -570,4 +613,30 @@ mod tests {
             KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT)
         );
     }
+
+    #[test]
+    fn test_keymap_preset_merge() {
+        let default_config: Config = json5::from_str(CONFIG).unwrap();
+        let preset: Config = json5::from_str(VIM_KEYMAP).unwrap();
+        let mut keybindings: KeyBindings =
+            json5::from_str(r#"{"Examination": {"<j>": "Quit"}}"#).unwrap();
+        keybindings.merge(&preset.keybindings);
+        keybindings.merge(&default_config.keybindings);
+
+        let examination = keybindings.get(&Mode::Examination).unwrap();
+        let key = |c| vec![KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty())];
+        // 用户配置优先于预设
+        assert_eq!(examination.get(&key('j')), Some(&Action::Quit));
+        assert_eq!(examination.get(&key('k')), Some(&Action::PrevQuestion));
+        assert_eq!(
+            examination.get(&vec![KeyEvent::new(KeyCode::Down, KeyModifiers::empty())]),
+            Some(&Action::NextQuestion)
+        );
+        // 终端输入 `:` 时可能附带 Shift
+        assert!(keybindings.is_bound(
+            Mode::Examination,
+            KeyEvent::new(KeyCode::Char(':'), KeyModifiers::SHIFT)
+        ));
+        assert!(!keybindings.is_bound(Mode::Examination, KeyEvent::from(KeyCode::Char('a'))));
+    }
 }
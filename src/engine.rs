@@ -0,0 +1,22 @@
+//! 与界面无关的考试引擎：读取题库、按考试配置组卷、记录作答并判分。
+//!
+//! 终端界面、局域网考试与 HTTP 接口都基于这里的数据类型，判分结果一致。
+
+pub mod audit;
+pub mod blank;
+pub mod code;
+#[cfg(test)]
+pub(crate) mod fixture;
+pub mod key;
+pub mod paper;
+pub mod question;
+pub mod signature;
+
+pub use audit::{AuditEvent, AuditKind, AuditLog, QuestionTime};
+pub use key::{AnswerKey, KeyError};
+pub use paper::{Bank, ExaminationConfig, LoadError, Paper, SuspendPolicy, load_bank};
+pub use question::{
+    FillIn, FillInItem, Group, InputError, InputKind, InvalidInput, Judge, MultiSelect, Question,
+    QuestionEnum, QuestionType, SingleSelect,
+};
+pub use signature::{SignError, Signed, SigningKey};
@@ -0,0 +1,192 @@
+//! 考试过程的审计记录：切出窗口、挂起、调整终端大小、浏览与修改作答等事件，附在成绩中供监考老师核查。
+
+use crate::engine::question::QuestionType;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AuditKind {
+    /// 终端失去焦点，通常意味着切换到了其他窗口
+    FocusLost,
+    FocusGained,
+    /// 尝试挂起，考试配置禁止挂起时只记录这一条
+    SuspendAttempt,
+    Suspended,
+    Resumed,
+    Resize {
+        width: u16,
+        height: u16,
+    },
+    /// 开始浏览某道题
+    Viewed {
+        question_type: QuestionType,
+        index: usize,
+    },
+    AnswerChanged {
+        question_type: QuestionType,
+        index: usize,
+        user_input: Vec<Option<String>>,
+    },
+    /// 交卷，附带考试界面统计的每道题用时
+    Submitted {
+        #[serde(default)]
+        time_spent: Vec<QuestionTime>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AuditEvent {
+    pub at: DateTime<Local>,
+    #[serde(flatten)]
+    pub kind: AuditKind,
+}
+
+impl AuditEvent {
+    pub fn now(kind: AuditKind) -> Self {
+        Self {
+            at: Local::now(),
+            kind,
+        }
+    }
+}
+
+/// 某道题的累计浏览时间
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct QuestionTime {
+    pub question_type: QuestionType,
+    pub index: usize,
+    pub seconds: i64,
+}
+
+/// 按时间顺序排列的审计事件
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct AuditLog(Vec<AuditEvent>);
+
+impl AuditLog {
+    pub fn push(&mut self, event: AuditEvent) {
+        self.0.push(event);
+    }
+
+    pub fn events(&self) -> &[AuditEvent] {
+        &self.0
+    }
+
+    /// 切出考试窗口的次数
+    pub fn focus_lost(&self) -> usize {
+        self.0
+            .iter()
+            .filter(|e| e.kind == AuditKind::FocusLost)
+            .count()
+    }
+
+    /// 每道题的用时：交卷时附带了统计结果则以它为准，否则按浏览记录估算
+    pub fn time_spent(&self) -> Vec<QuestionTime> {
+        self.0
+            .iter()
+            .rev()
+            .find_map(|e| match &e.kind {
+                AuditKind::Submitted { time_spent } if !time_spent.is_empty() => {
+                    Some(time_spent.clone())
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| self.viewing_time())
+    }
+
+    /// 按浏览记录估算每道题的用时，按第一次浏览的顺序排列；失去焦点与挂起期间不计时，交卷后停止计时
+    fn viewing_time(&self) -> Vec<QuestionTime> {
+        let mut times: Vec<QuestionTime> = vec![];
+        let mut current: Option<(QuestionType, usize)> = None;
+        let mut since: Option<DateTime<Local>> = None;
+        for event in &self.0 {
+            // 结束当前题目的计时
+            let stops = matches!(
+                event.kind,
+                AuditKind::Viewed { .. }
+                    | AuditKind::FocusLost
+                    | AuditKind::Suspended
+                    | AuditKind::Submitted { .. }
+            );
+            if stops && let (Some((question_type, index)), Some(start)) = (current, since) {
+                let seconds = (event.at - start).num_seconds();
+                match times
+                    .iter_mut()
+                    .find(|t| (t.question_type, t.index) == (question_type, index))
+                {
+                    Some(time) => time.seconds += seconds,
+                    None => times.push(QuestionTime {
+                        question_type,
+                        index,
+                        seconds,
+                    }),
+                }
+                since = None;
+            }
+            match event.kind {
+                AuditKind::Viewed {
+                    question_type,
+                    index,
+                } => {
+                    current = Some((question_type, index));
+                    since = Some(event.at);
+                }
+                AuditKind::FocusGained | AuditKind::Resumed if current.is_some() => {
+                    since = Some(event.at)
+                }
+                AuditKind::Submitted { .. } => current = None,
+                _ => {}
+            }
+        }
+        times
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::audit::{AuditEvent, AuditKind, AuditLog, QuestionTime};
+    use crate::engine::question::QuestionType;
+    use chrono::{Local, TimeDelta};
+
+    #[test]
+    fn test_time_spent() {
+        let start = Local::now();
+        let viewed = |index| AuditKind::Viewed {
+            question_type: QuestionType::Judge,
+            index,
+        };
+        let mut log = AuditLog::default();
+        for (seconds, kind) in [
+            (0, viewed(0)),
+            (10, viewed(1)),
+            // 切出窗口的 30 秒不计入
+            (15, AuditKind::FocusLost),
+            (45, AuditKind::FocusGained),
+            (50, viewed(0)),
+            (55, AuditKind::Submitted { time_spent: vec![] }),
+            (70, AuditKind::FocusLost),
+        ] {
+            log.push(AuditEvent {
+                at: start + TimeDelta::seconds(seconds),
+                kind,
+            });
+        }
+        let time = |index, seconds| QuestionTime {
+            question_type: QuestionType::Judge,
+            index,
+            seconds,
+        };
+        assert_eq!(log.time_spent(), vec![time(0, 15), time(1, 10)]);
+        assert_eq!(log.focus_lost(), 2);
+
+        // 交卷时附带的统计结果优先
+        log.push(AuditEvent::now(AuditKind::Submitted {
+            time_spent: vec![time(1, 12)],
+        }));
+        assert_eq!(log.time_spent(), vec![time(1, 12)]);
+
+        let json = serde_json::to_string(&log.events()[3]).unwrap();
+        assert!(json.contains(r#""event":"focus_gained""#));
+    }
+}
@@ -0,0 +1,160 @@
+//! 题干中的填空位置：支持显式占位符 `{{blank}}`、三个及以上的下划线 `___`、
+//! 配置中自定义的占位符，以及旧格式的中英文空括号 `（ ）`、`( )`。
+//!
+//! 正文中的 `\(`、`\（`、`\_`、`\{` 等转义为字面字符，代码块中的内容不作为填空。
+
+use crate::engine::code;
+use std::sync::OnceLock;
+
+const PLACEHOLDER: &str = "{{blank}}";
+const ESCAPABLE: &[char] = &['(', ')', '（', '）', '_', '{', '}', '\\'];
+
+static CUSTOM_PLACEHOLDER: OnceLock<Option<String>> = OnceLock::new();
+
+/// 设置自定义的占位符，只有第一次调用生效
+pub fn set_placeholder(placeholder: Option<String>) {
+    let _ = CUSTOM_PLACEHOLDER.set(placeholder.filter(|p| !p.is_empty()));
+}
+
+/// 填空位置两侧显示的括号
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Blank {
+    pub open: &'static str,
+    pub close: &'static str,
+}
+
+impl Blank {
+    const CN: Blank = Blank {
+        open: "（",
+        close: "）",
+    };
+    const EN: Blank = Blank {
+        open: "(",
+        close: ")",
+    };
+}
+
+/// 切分后的题干，`parts` 比 `blanks` 多一项，两者交替排列
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Stem {
+    pub parts: Vec<String>,
+    pub blanks: Vec<Blank>,
+}
+
+impl Stem {
+    /// 没有填空位置时在题干末尾补一个，保证作答内容总能显示
+    pub fn default_blank(&self) -> Blank {
+        self.blanks.first().copied().unwrap_or_else(|| {
+            if self.parts.iter().any(|p| has_cjk(p)) {
+                Blank::CN
+            } else {
+                Blank::EN
+            }
+        })
+    }
+}
+
+fn has_cjk(text: &str) -> bool {
+    text.chars()
+        .any(|c| matches!(c, '\u{3000}'..='\u{303f}' | '\u{4e00}'..='\u{9fff}' | '\u{ff00}'..='\u{ffef}'))
+}
+
+/// 从 `rest` 开头匹配一个空括号，返回匹配的字节长度
+fn empty_parentheses(rest: &str, open: char, close: char) -> Option<usize> {
+    let inner = rest.strip_prefix(open)?;
+    let trimmed = inner.trim_start();
+    trimmed
+        .starts_with(close)
+        .then(|| rest.len() - trimmed.len() + close.len_utf8())
+}
+
+/// 按填空位置切分题干，同时去掉转义符
+pub fn parse(question: &str) -> Stem {
+    let custom = CUSTOM_PLACEHOLDER.get().cloned().flatten();
+    let mut parts = vec![String::new()];
+    let mut blanks = vec![];
+    for (is_code, segment) in code::segments(question) {
+        if is_code {
+            parts.last_mut().unwrap().push_str(segment);
+            continue;
+        }
+        let mut rest = segment;
+        while let Some(c) = rest.chars().next() {
+            let (blank, len) = if c == '\\' {
+                match rest[1..].chars().next().filter(|n| ESCAPABLE.contains(n)) {
+                    Some(n) => {
+                        parts.last_mut().unwrap().push(n);
+                        rest = &rest[1 + n.len_utf8()..];
+                        continue;
+                    }
+                    None => (None, 1),
+                }
+            } else if let Some(p) = custom.as_deref().filter(|p| rest.starts_with(p)) {
+                (Some(None), p.len())
+            } else if rest.starts_with(PLACEHOLDER) {
+                (Some(None), PLACEHOLDER.len())
+            } else if rest.starts_with("___") {
+                (Some(None), rest.len() - rest.trim_start_matches('_').len())
+            } else if let Some(len) = empty_parentheses(rest, '（', '）') {
+                (Some(Some(Blank::CN)), len)
+            } else if let Some(len) = empty_parentheses(rest, '(', ')') {
+                (Some(Some(Blank::EN)), len)
+            } else {
+                (None, c.len_utf8())
+            };
+            match blank {
+                Some(blank) => {
+                    blanks.push(blank);
+                    parts.push(String::new());
+                }
+                None => parts.last_mut().unwrap().push_str(&rest[..len]),
+            }
+            rest = &rest[len..];
+        }
+    }
+    // 显式占位符没有自带括号，沿用题干中的括号，否则按题干语言选择
+    let mut stem = Stem {
+        parts,
+        blanks: blanks.iter().flatten().copied().collect(),
+    };
+    let fallback = stem.default_blank();
+    stem.blanks = blanks
+        .into_iter()
+        .map(|blank| blank.unwrap_or(fallback))
+        .collect();
+    stem
+}
+
+/// 题干中填空位置的数量
+pub fn count(question: &str) -> usize {
+    parse(question).blanks.len()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::blank::{Blank, count, parse};
+
+    #[test]
+    fn test_parse() {
+        let stem = parse("Mixed （ ） and ( ) and {{blank}} and ____ end");
+        assert_eq!(
+            stem.parts,
+            vec!["Mixed ", " and ", " and ", " and ", " end"]
+        );
+        assert_eq!(
+            stem.blanks,
+            vec![Blank::CN, Blank::EN, Blank::CN, Blank::CN]
+        );
+
+        let stem = parse("call f\\(\\) and f(x), _a_ or \\___ here ( )");
+        assert_eq!(stem.parts, vec!["call f() and f(x), _a_ or ___ here ", ""]);
+        assert_eq!(stem.blanks, vec![Blank::EN]);
+
+        let stem = parse("The answer is {{blank}}.");
+        assert_eq!(stem.blanks, vec![Blank::EN]);
+
+        // 代码块中的括号与问号括号都不是填空
+        assert_eq!(count("阅读代码：\n```\nf()\n```\n结果是（？）"), 0);
+        assert_eq!(parse("没有填空").default_blank(), Blank::CN);
+    }
+}
@@ -0,0 +1,54 @@
+//! 题干与选项中用 ``` 围起来的代码块。
+
+const FENCE: &str = "```";
+
+/// 是否为代码块的起止行
+pub fn is_fence(line: &str) -> bool {
+    line.trim_start().starts_with(FENCE)
+}
+
+/// 将文本切分为 (是否为代码块, 片段) 序列，代码块片段包含首尾的 ``` 行
+pub fn segments(text: &str) -> Vec<(bool, &str)> {
+    let mut segments = vec![];
+    let mut in_code = false;
+    let mut start = 0;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if is_fence(line) {
+            if in_code {
+                segments.push((true, &text[start..offset + line.len()]));
+                start = offset + line.len();
+            } else {
+                if start < offset {
+                    segments.push((false, &text[start..offset]));
+                }
+                start = offset;
+            }
+            in_code = !in_code;
+        }
+        offset += line.len();
+    }
+    if start < text.len() {
+        segments.push((in_code, &text[start..]));
+    }
+    segments
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::code::segments;
+
+    #[test]
+    fn test_segments() {
+        let question = "阅读代码：\n```rust\nfn main() {}\n```\n输出是（ ）";
+        let segments = segments(question);
+        assert_eq!(
+            segments,
+            vec![
+                (false, "阅读代码：\n"),
+                (true, "```rust\nfn main() {}\n```\n"),
+                (false, "输出是（ ）"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,24 @@
+//! 测试共用的考试配置与题库。
+//!
+//! 终端界面所在的可执行文件通过 `#[path]` 引入同一份文件，因此这里只依赖 serde，不引用 crate 内的路径。
+
+use serde::de::DeserializeOwned;
+
+/// 单选题与判断题各一道的考试
+pub const EXAM: &str =
+    r#"{"name":"quiz","duration":60,"single_select":1,"multi_select":0,"judge":1,"fill_in":0}"#;
+
+/// 单选、判断、填空题各一道的题库，单选题满分 2 分，其余 1 分
+pub const BANK: &str = r#"{
+    "SingleSelect":[{"SingleSelect":{"question":"1 + 1 = ( )","options":["A: 1","B: 2"],"answer":"B","score":2,"explanation":"1 + 1 = 2"}}],
+    "Judge":[{"Judge":{"question":"2 > 3 ( )","answer":"No","score":1,"explanation":"2 < 3"}}],
+    "FillIn":[{"FillIn":{"question":"{} + {} = 3","items":[{"answer":"1","score":1},{"answer":"2","score":1}]}}]
+}"#;
+
+pub fn exam<T: DeserializeOwned>() -> T {
+    serde_json::from_str(EXAM).unwrap()
+}
+
+pub fn bank<T: DeserializeOwned>() -> T {
+    serde_json::from_str(BANK).unwrap()
+}
@@ -0,0 +1,217 @@
+//! 答案密钥：题库中的答案与解析单独加密保存，考生机器上只留不含答案的题库，判分时由监考老师输入口令解锁。
+
+use crate::engine::paper::{Bank, Paper};
+use crate::engine::question::QuestionEnum;
+use argon2::Argon2;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// 解锁后的答案密钥，按题目编号索引带答案与解析的完整题目
+#[derive(Debug, Clone, Default)]
+pub struct AnswerKey {
+    questions: HashMap<String, QuestionEnum>,
+}
+
+/// 密钥文件的内容，口令经 Argon2 派生密钥后用 ChaCha20-Poly1305 加密
+#[derive(Serialize, Deserialize)]
+struct Sealed {
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// 读取或解锁答案密钥失败
+#[derive(Debug)]
+pub enum KeyError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// 口令错误或密钥文件已损坏
+    Locked,
+}
+
+impl Display for KeyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyError::Io(e) => e.fmt(f),
+            KeyError::Json(e) => e.fmt(f),
+            KeyError::Locked => f.write_str("wrong password or damaged answer key"),
+        }
+    }
+}
+
+impl std::error::Error for KeyError {}
+
+impl From<std::io::Error> for KeyError {
+    fn from(e: std::io::Error) -> Self {
+        KeyError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for KeyError {
+    fn from(e: serde_json::Error) -> Self {
+        KeyError::Json(e)
+    }
+}
+
+impl AnswerKey {
+    pub fn new(bank: &Bank) -> Self {
+        let questions = bank
+            .values()
+            .flatten()
+            .map(|q| (q.id(), q.clone()))
+            .collect();
+        Self { questions }
+    }
+
+    /// 用口令加密，返回密钥文件的内容
+    pub fn seal(&self, password: &str) -> Result<Vec<u8>, KeyError> {
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::rng().fill(&mut salt);
+        rand::rng().fill(&mut nonce);
+        let plaintext = serde_json::to_vec(&self.questions)?;
+        let ciphertext = cipher(password, &salt)?
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+            .map_err(|_| KeyError::Locked)?;
+        let sealed = Sealed {
+            salt: STANDARD.encode(salt),
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(ciphertext),
+        };
+        Ok(serde_json::to_vec_pretty(&sealed)?)
+    }
+
+    /// 用口令解锁密钥文件的内容
+    pub fn unlock(sealed: &[u8], password: &str) -> Result<Self, KeyError> {
+        let sealed: Sealed = serde_json::from_slice(sealed)?;
+        let decode = |s: &str| STANDARD.decode(s).map_err(|_| KeyError::Locked);
+        let nonce = decode(&sealed.nonce)?;
+        if nonce.len() != NONCE_LEN {
+            return Err(KeyError::Locked);
+        }
+        let plaintext = cipher(password, &decode(&sealed.salt)?)?
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                decode(&sealed.ciphertext)?.as_slice(),
+            )
+            .map_err(|_| KeyError::Locked)?;
+        Ok(Self {
+            questions: serde_json::from_slice(&plaintext)?,
+        })
+    }
+
+    /// 读取并解锁密钥文件
+    pub fn load(path: &Path, password: &str) -> Result<Self, KeyError> {
+        Self::unlock(&fs::read(path)?, password)
+    }
+
+    /// 换回带答案的题目并保留作答内容，密钥中没有的题目原样返回
+    pub fn restore(&self, question: &QuestionEnum) -> QuestionEnum {
+        match self.questions.get(&question.id()) {
+            Some(q) => {
+                let mut q = q.clone();
+                q.set_user_input(question.user_input());
+                q
+            }
+            None => question.clone(),
+        }
+    }
+
+    /// 换回整份试卷的答案与解析，之后即可判分
+    pub fn restore_paper(&self, paper: &Paper) -> Paper {
+        paper
+            .iter()
+            .map(|(question_type, questions)| {
+                let questions = questions.iter().map(|q| self.restore(q)).collect();
+                (*question_type, questions)
+            })
+            .collect()
+    }
+
+    /// 换回整个题库的答案与解析
+    pub fn restore_bank(&self, bank: &Bank) -> Bank {
+        bank.iter()
+            .map(|(question_type, questions)| {
+                let questions = questions.iter().map(|q| self.restore(q)).collect();
+                (*question_type, questions)
+            })
+            .collect()
+    }
+}
+
+/// 拆分题库：返回不含答案的题库与用口令加密的密钥文件内容
+pub fn seal_bank(bank: &Bank, password: &str) -> Result<(Bank, Vec<u8>), KeyError> {
+    let sealed = AnswerKey::new(bank).seal(password)?;
+    let redacted = bank
+        .iter()
+        .map(|(question_type, questions)| {
+            let questions = questions
+                .iter()
+                .map(QuestionEnum::without_answers)
+                .collect();
+            (*question_type, questions)
+        })
+        .collect();
+    Ok((redacted, sealed))
+}
+
+fn cipher(password: &str, salt: &[u8]) -> Result<ChaCha20Poly1305, KeyError> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|_| KeyError::Locked)?;
+    Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::fixture;
+    use crate::engine::key::{self, AnswerKey, KeyError};
+    use crate::engine::paper::{self, Bank, Paper};
+    use crate::engine::question::QuestionType;
+
+    #[test]
+    fn test_seal_and_unlock() {
+        let bank: Bank = fixture::bank();
+        let (redacted, sealed) = key::seal_bank(&bank, "secret").unwrap();
+        // 拆分后的题库与密钥文件中都找不到明文答案
+        let text = serde_json::to_string(&redacted).unwrap();
+        assert!(!text.contains("1 + 1 = 2"));
+        assert!(!String::from_utf8_lossy(&sealed).contains("1 + 1 = 2"));
+        assert!(matches!(
+            AnswerKey::unlock(&sealed, "wrong"),
+            Err(KeyError::Locked)
+        ));
+
+        let mut paper: Paper = vec![
+            (
+                QuestionType::SingleSelect,
+                redacted[&QuestionType::SingleSelect].clone(),
+            ),
+            (
+                QuestionType::FillIn,
+                redacted[&QuestionType::FillIn].clone(),
+            ),
+        ];
+        paper[0].1[0].set_user_input(vec![Some("B".to_string())]);
+        paper[1].1[0].set_user_input(vec![Some("1".to_string()), Some("3".to_string())]);
+        assert_eq!(paper::score(&paper), 0);
+
+        let answer_key = AnswerKey::unlock(&sealed, "secret").unwrap();
+        let restored = answer_key.restore_paper(&paper);
+        assert_eq!(paper::score(&restored), 3);
+        assert_eq!(restored[0].1[0].explanation().as_deref(), Some("1 + 1 = 2"));
+        assert_eq!(answer_key.restore_bank(&redacted), bank);
+    }
+}
@@ -0,0 +1,268 @@
+//! 考试配置、题库读取与随机组卷。
+
+use crate::engine::question::{QuestionEnum, QuestionType};
+use linked_hash_map::LinkedHashMap;
+use rand::rng;
+use rand::seq::IndexedRandom;
+use serde::{Deserialize, Serialize};
+use std::cmp::min;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use tracing::warn;
+
+/// 按题型分组的完整题库
+pub type Bank = HashMap<QuestionType, Vec<QuestionEnum>>;
+
+/// 按题型顺序排列的试卷
+pub type Paper = Vec<(QuestionType, Vec<QuestionEnum>)>;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ExaminationConfig {
+    name: String,
+    duration: u64,
+    single_select: usize,
+    multi_select: usize,
+    judge: usize,
+    fill_in: usize,
+    #[serde(default)]
+    group: usize,
+    /// 练习模式：每道题作答后立即判分并显示解析，不计时
+    #[serde(default)]
+    practice: bool,
+    /// 练习模式下，判分后是否锁定答案
+    #[serde(default)]
+    lock_answer: bool,
+    /// 考试期间能否挂起
+    #[serde(default)]
+    suspend: SuspendPolicy,
+    /// 每道题的作答时限（秒），超时后自动跳到下一题且不能再修改
+    #[serde(default)]
+    question_time_limit: Option<u64>,
+}
+
+/// 考试期间挂起（Ctrl-z）的处理方式，每次尝试都会记入审计记录
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SuspendPolicy {
+    #[default]
+    Allow,
+    /// 先提示挂起会被记录，确认后才挂起
+    Warn,
+    Forbid,
+}
+
+/// 读取考试配置或题库失败
+#[derive(Debug)]
+pub enum LoadError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(e) => e.fmt(f),
+            LoadError::Json(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl From<std::io::Error> for LoadError {
+    fn from(e: std::io::Error) -> Self {
+        LoadError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for LoadError {
+    fn from(e: serde_json::Error) -> Self {
+        LoadError::Json(e)
+    }
+}
+
+impl ExaminationConfig {
+    /// 读取考试配置文件
+    pub fn load(path: &Path) -> Result<Self, LoadError> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 考试时长（秒）
+    pub fn duration(&self) -> u64 {
+        self.duration
+    }
+
+    pub fn practice(&self) -> bool {
+        self.practice
+    }
+
+    pub fn set_practice(&mut self, practice: bool) {
+        self.practice = practice;
+    }
+
+    pub fn lock_answer(&self) -> bool {
+        self.lock_answer
+    }
+
+    pub fn suspend(&self) -> SuspendPolicy {
+        self.suspend
+    }
+
+    /// 每道题的作答时限，练习模式不限时
+    pub fn question_time_limit(&self) -> Option<Duration> {
+        self.question_time_limit
+            .filter(|_| !self.practice)
+            .map(Duration::from_secs)
+    }
+}
+
+/// 读取完整题库，填空位置与答案数量不一致的题目仍可作答，只记录警告
+pub fn load_bank(path: &Path) -> Result<Bank, LoadError> {
+    let bank = serde_json::from_slice::<Bank>(&fs::read(path)?)?;
+    // 填空位置与答案数量不一致时仍可显示，多出的答案补在题干末尾
+    for result in bank.values().flatten().map(QuestionEnum::check_blanks) {
+        if let Err(e) = result {
+            warn!("{e}");
+        }
+    }
+    Ok(bank)
+}
+
+/// 试卷得分，终端界面、局域网考试与 HTTP 接口共用
+pub fn score(paper: &Paper) -> u16 {
+    paper
+        .iter()
+        .flat_map(|(_, questions)| questions)
+        .map(QuestionEnum::cal_score)
+        .sum()
+}
+
+/// 试卷满分
+pub fn full_score(paper: &Paper) -> u16 {
+    paper
+        .iter()
+        .flat_map(|(_, questions)| questions)
+        .map(QuestionEnum::full_score)
+        .sum()
+}
+
+/// 去掉整份试卷的答案与解析
+pub fn without_answers(paper: &Paper) -> Paper {
+    paper
+        .iter()
+        .map(|(question_type, questions)| {
+            let questions = questions
+                .iter()
+                .map(QuestionEnum::without_answers)
+                .collect();
+            (*question_type, questions)
+        })
+        .collect()
+}
+
+impl QuestionEnum {
+    /// 按考试配置从题库中随机抽题组卷
+    pub fn choose(
+        type_2_questions: &Bank,
+        ec: &ExaminationConfig,
+    ) -> LinkedHashMap<QuestionType, Vec<QuestionEnum>> {
+        let mut questions = LinkedHashMap::new();
+        let single_select: Vec<QuestionEnum> = Self::random_choose_question(
+            type_2_questions,
+            QuestionType::SingleSelect,
+            ec.single_select,
+        );
+        questions.insert(QuestionType::SingleSelect, single_select);
+        let multi_select: Vec<QuestionEnum> = Self::random_choose_question(
+            type_2_questions,
+            QuestionType::MultiSelect,
+            ec.multi_select,
+        );
+        questions.insert(QuestionType::MultiSelect, multi_select);
+        let judge: Vec<QuestionEnum> =
+            Self::random_choose_question(type_2_questions, QuestionType::Judge, ec.judge);
+        questions.insert(QuestionType::Judge, judge);
+        let fill_in: Vec<QuestionEnum> =
+            Self::random_choose_question(type_2_questions, QuestionType::FillIn, ec.fill_in);
+        questions.insert(QuestionType::FillIn, fill_in);
+        // 材料题作为一个整体抽取，子题不会被拆散
+        let group: Vec<QuestionEnum> =
+            Self::random_choose_question(type_2_questions, QuestionType::Group, ec.group);
+        questions.insert(QuestionType::Group, group);
+        questions
+    }
+
+    fn random_choose_question(
+        question_name_2_vec: &Bank,
+        question_type: QuestionType,
+        question_size: usize,
+    ) -> Vec<QuestionEnum> {
+        question_name_2_vec
+            .get(&question_type)
+            .map(|v| {
+                let n = min(v.len(), question_size);
+                let mut rng = rng();
+                v.choose_multiple(&mut rng, n).cloned().collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::fixture;
+    use crate::engine::paper::{self, Bank, ExaminationConfig, Paper};
+    use crate::engine::question::{QuestionEnum, QuestionType};
+    use std::time::Duration;
+
+    #[test]
+    fn test_choose_and_score() {
+        let mut exam: ExaminationConfig = fixture::exam();
+        exam.single_select = 5;
+        let bank: Bank = fixture::bank();
+        let mut paper: Paper = QuestionEnum::choose(&bank, &exam).into_iter().collect();
+        // 题库中题目不足时只抽取现有的题目，缺少的题型为空
+        let counts = paper
+            .iter()
+            .map(|(t, qs)| (*t, qs.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(counts[0], (QuestionType::SingleSelect, 1));
+        assert_eq!(counts[2], (QuestionType::Judge, 1));
+        assert_eq!(counts.len(), 5);
+
+        for (_, questions) in paper.iter_mut() {
+            for q in questions.iter_mut() {
+                q.set_user_input(vec![Some("B".to_string())]);
+            }
+        }
+        assert_eq!((paper::score(&paper), paper::full_score(&paper)), (2, 3));
+
+        let redacted = paper::without_answers(&paper);
+        assert_eq!(paper::score(&redacted), 0);
+        assert!(
+            redacted
+                .iter()
+                .flat_map(|(_, qs)| qs)
+                .all(|q| q.explanation().is_none())
+        );
+    }
+
+    #[test]
+    fn test_question_time_limit() {
+        let mut exam: ExaminationConfig = serde_json::from_str(
+            r#"{"name":"quiz","duration":60,"single_select":1,"multi_select":0,"judge":0,"fill_in":0,"question_time_limit":30}"#,
+        )
+        .unwrap();
+        assert_eq!(exam.question_time_limit(), Some(Duration::from_secs(30)));
+        // 练习模式不限时
+        exam.set_practice(true);
+        assert_eq!(exam.question_time_limit(), None);
+    }
+}
@@ -0,0 +1,864 @@
+//! 题目的数据结构、作答与判分。
+
+use crate::engine::blank;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+use strum::{EnumIter, FromRepr};
+
+/// 单个作答内容的题目（单选、多选、判断）共用的判分逻辑
+pub trait Question {
+    fn cal_score(&self) -> u16 {
+        match self.user_input() {
+            None => 0,
+            Some(user_input) if Self::check_answer(user_input.clone(), self.answer()) => {
+                self.score()
+            }
+            Some(_) => 0,
+        }
+    }
+
+    fn check_answer(user_input: String, answer: String) -> bool {
+        let user_input_set = user_input
+            .chars()
+            .map(|c| c.to_lowercase().to_string())
+            .collect::<HashSet<_>>();
+        let answer_set = answer
+            .chars()
+            .map(|c| c.to_lowercase().to_string())
+            .collect::<HashSet<_>>();
+        user_input_set == answer_set
+    }
+
+    fn user_input(&self) -> Option<String>;
+
+    fn answer(&self) -> String;
+    fn question(&self) -> String;
+
+    fn score(&self) -> u16;
+
+    fn answered(&self) -> bool;
+}
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Deserialize, Serialize, Hash, FromRepr, EnumIter)]
+pub enum QuestionType {
+    SingleSelect,
+    MultiSelect,
+    Judge,
+    FillIn,
+    Group,
+}
+
+impl QuestionType {
+    /// Get the previous tab, if there is no previous tab return the current tab.
+    pub fn previous(self) -> Self {
+        let current_index: usize = self as usize;
+        let previous_index = current_index.saturating_sub(1);
+        Self::from_repr(previous_index).unwrap_or(self)
+    }
+
+    /// Get the next tab, if there is no next tab return the current tab.
+    pub fn next(self) -> Self {
+        let current_index = self as usize;
+        let next_index = current_index.saturating_add(1);
+        Self::from_repr(next_index).unwrap_or(self)
+    }
+
+    /// 题型的英文键，用于主题样式与文字目录
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::SingleSelect => "single_select",
+            Self::MultiSelect => "multi_select",
+            Self::Judge => "judge",
+            Self::FillIn => "fill_in",
+            Self::Group => "group",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum QuestionEnum {
+    SingleSelect(SingleSelect),
+    MultiSelect(MultiSelect),
+    Judge(Judge),
+    FillIn(FillIn),
+    /// 材料题：一段共享的题干材料，下挂若干任意类型的子题
+    Group(Group),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SingleSelect {
+    pub question: String,
+    pub options: Vec<String>,
+    pub answer: String,
+    pub user_input: Option<String>,
+    pub score: u16,
+    #[serde(default)]
+    pub explanation: Option<String>,
+    #[serde(default)]
+    pub reference: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MultiSelect {
+    pub question: String,
+    pub options: Vec<String>,
+    pub answer: String,
+    pub user_input: Option<String>,
+    pub score: u16,
+    #[serde(default)]
+    pub explanation: Option<String>,
+    #[serde(default)]
+    pub reference: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Judge {
+    pub question: String,
+    pub answer: String,
+    pub user_input: Option<String>,
+    pub score: u16,
+    #[serde(default)]
+    pub explanation: Option<String>,
+    #[serde(default)]
+    pub reference: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FillIn {
+    pub question: String,
+    pub items: Vec<FillInItem>,
+    #[serde(default)]
+    pub explanation: Option<String>,
+    #[serde(default)]
+    pub reference: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FillInItem {
+    pub answer: String,
+    pub user_input: Option<String>,
+    pub score: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Group {
+    /// 共享的材料或代码片段
+    pub question: String,
+    /// 子题，作答与计分时按顺序展开
+    pub questions: Vec<QuestionEnum>,
+    #[serde(default)]
+    pub explanation: Option<String>,
+    #[serde(default)]
+    pub reference: Option<String>,
+}
+
+impl QuestionEnum {
+    /// 交卷后展示的答案解析
+    pub fn explanation(&self) -> Option<String> {
+        match self {
+            QuestionEnum::SingleSelect(q) => q.explanation.clone(),
+            QuestionEnum::MultiSelect(q) => q.explanation.clone(),
+            QuestionEnum::Judge(q) => q.explanation.clone(),
+            QuestionEnum::FillIn(q) => q.explanation.clone(),
+            QuestionEnum::Group(q) => q.explanation.clone(),
+        }
+    }
+
+    /// 解析的参考出处，可以是链接或文字说明
+    pub fn reference(&self) -> Option<String> {
+        match self {
+            QuestionEnum::SingleSelect(q) => q.reference.clone(),
+            QuestionEnum::MultiSelect(q) => q.reference.clone(),
+            QuestionEnum::Judge(q) => q.reference.clone(),
+            QuestionEnum::FillIn(q) => q.reference.clone(),
+            QuestionEnum::Group(q) => q.reference.clone(),
+        }
+    }
+
+    pub fn cal_score(&self) -> u16 {
+        match self {
+            QuestionEnum::SingleSelect(q) => q.cal_score(),
+            QuestionEnum::MultiSelect(q) => q.cal_score(),
+            QuestionEnum::Judge(q) => q.cal_score(),
+            QuestionEnum::FillIn(q) => q.cal_score(),
+            QuestionEnum::Group(q) => q.cal_score(),
+        }
+    }
+
+    pub fn user_input(&self) -> Vec<Option<String>> {
+        match self {
+            QuestionEnum::SingleSelect(q) => vec![q.user_input()],
+            QuestionEnum::MultiSelect(q) => vec![q.user_input()],
+            QuestionEnum::Judge(q) => vec![q.user_input()],
+            QuestionEnum::FillIn(q) => q.user_input(),
+            QuestionEnum::Group(q) => q.user_input(),
+        }
+    }
+
+    pub fn answered(&self) -> bool {
+        match self {
+            QuestionEnum::SingleSelect(q) => q.answered(),
+            QuestionEnum::MultiSelect(q) => q.answered(),
+            QuestionEnum::Judge(q) => q.answered(),
+            QuestionEnum::FillIn(q) => q.answered(),
+            QuestionEnum::Group(q) => q.answered(),
+        }
+    }
+
+    pub fn set_user_input(&mut self, user_input: Vec<Option<String>>) {
+        match self {
+            QuestionEnum::SingleSelect(q) => {
+                q.user_input = user_input.first().cloned().flatten();
+            }
+            QuestionEnum::MultiSelect(q) => {
+                q.user_input = user_input.first().cloned().flatten();
+            }
+            QuestionEnum::Judge(q) => {
+                q.user_input = user_input.first().cloned().flatten();
+            }
+            QuestionEnum::FillIn(q) => {
+                q.items
+                    .iter_mut()
+                    .zip(user_input.iter())
+                    .for_each(|(item, user_input)| item.user_input = user_input.clone());
+            }
+            QuestionEnum::Group(q) => {
+                // 按子题的输入框数量依次切分答案
+                let mut rest = user_input.as_slice();
+                for child in q.questions.iter_mut() {
+                    let (head, tail) = rest.split_at(child.input_size().min(rest.len()));
+                    child.set_user_input(head.to_vec());
+                    rest = tail;
+                }
+            }
+        }
+    }
+
+    /// 去掉答案、解析与参考出处，用于把试卷发给考生，已作答的内容保留
+    pub fn without_answers(&self) -> QuestionEnum {
+        let mut q = self.clone();
+        match &mut q {
+            QuestionEnum::SingleSelect(q) => {
+                q.answer.clear();
+                (q.explanation, q.reference) = (None, None);
+            }
+            QuestionEnum::MultiSelect(q) => {
+                q.answer.clear();
+                (q.explanation, q.reference) = (None, None);
+            }
+            QuestionEnum::Judge(q) => {
+                q.answer.clear();
+                (q.explanation, q.reference) = (None, None);
+            }
+            QuestionEnum::FillIn(q) => {
+                q.items.iter_mut().for_each(|item| item.answer.clear());
+                (q.explanation, q.reference) = (None, None);
+            }
+            QuestionEnum::Group(q) => {
+                q.questions = q
+                    .questions
+                    .iter()
+                    .map(QuestionEnum::without_answers)
+                    .collect();
+                (q.explanation, q.reference) = (None, None);
+            }
+        }
+        q
+    }
+
+    /// 检查题干中的填空位置数量：填空题须与 `items` 一致，其他题型至多一个
+    pub fn check_blanks(&self) -> Result<(), String> {
+        let (question, expected) = match self {
+            QuestionEnum::SingleSelect(q) => (&q.question, None),
+            QuestionEnum::MultiSelect(q) => (&q.question, None),
+            QuestionEnum::Judge(q) => (&q.question, None),
+            QuestionEnum::FillIn(q) => (&q.question, Some(q.items.len())),
+            QuestionEnum::Group(q) => {
+                return q.questions.iter().try_for_each(QuestionEnum::check_blanks);
+            }
+        };
+        let count = blank::count(question);
+        match expected {
+            Some(expected) if count != expected => Err(format!(
+                "{expected} answers but {count} blanks in question: {question}"
+            )),
+            None if count > 1 => Err(format!("{count} blanks in question: {question}")),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn input_size(&self) -> usize {
+        match self {
+            QuestionEnum::SingleSelect(_) => 1,
+            QuestionEnum::MultiSelect(_) => 1,
+            QuestionEnum::Judge(_) => 1,
+            QuestionEnum::FillIn(q) => q.items.len(),
+            QuestionEnum::Group(q) => q.questions.iter().map(QuestionEnum::input_size).sum(),
+        }
+    }
+
+    /// 选择题的选项，其他题型为空
+    pub fn options(&self) -> Vec<String> {
+        match self {
+            QuestionEnum::SingleSelect(q) => q.options.clone(),
+            QuestionEnum::MultiSelect(q) => q.options.clone(),
+            _ => vec![],
+        }
+    }
+
+    /// 选中（单选题）或切换（多选题）第 idx 个选项，超出选项范围时返回 false
+    pub fn toggle_option(&mut self, idx: usize) -> bool {
+        let Some(letter) = to_letter(idx).filter(|_| idx < self.options().len()) else {
+            return false;
+        };
+        match self {
+            QuestionEnum::SingleSelect(q) => {
+                q.user_input = Some(letter.to_string());
+                true
+            }
+            QuestionEnum::MultiSelect(q) => {
+                let mut letters = q
+                    .user_input
+                    .clone()
+                    .unwrap_or_default()
+                    .to_uppercase()
+                    .chars()
+                    .collect::<Vec<char>>();
+                match letters.iter().position(|c| *c == letter) {
+                    Some(pos) => {
+                        letters.remove(pos);
+                    }
+                    None => letters.push(letter),
+                }
+                letters.sort();
+                q.user_input = (!letters.is_empty()).then(|| letters.into_iter().collect());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 每个输入框期望的输入类型，与 `user_input` 一一对应
+    pub fn input_kinds(&self) -> Vec<InputKind> {
+        match self {
+            QuestionEnum::SingleSelect(q) => vec![InputKind::Single(q.options.len())],
+            QuestionEnum::MultiSelect(q) => vec![InputKind::Multi(q.options.len())],
+            QuestionEnum::Judge(_) => vec![InputKind::Judge],
+            QuestionEnum::FillIn(q) => vec![InputKind::Text; q.items.len()],
+            QuestionEnum::Group(q) => q
+                .questions
+                .iter()
+                .flat_map(QuestionEnum::input_kinds)
+                .collect(),
+        }
+    }
+
+    /// 按题型校验并规范化全部输入框，缺少的输入框视为未作答
+    pub fn normalize_input(
+        &self,
+        user_input: &[Option<String>],
+    ) -> Result<Vec<Option<String>>, InvalidInput> {
+        let kinds = self.input_kinds();
+        if user_input.len() > kinds.len() {
+            return Err(InvalidInput {
+                index: kinds.len(),
+                error: InputError::TooMany {
+                    expected: kinds.len(),
+                },
+            });
+        }
+        kinds
+            .iter()
+            .enumerate()
+            .map(|(index, kind)| {
+                let input = user_input.get(index).cloned().flatten().unwrap_or_default();
+                kind.normalize(&input)
+                    .map_err(|error| InvalidInput { index, error })
+            })
+            .collect()
+    }
+
+    /// 校验并规范化后保存作答，不合法时保留原来的作答。
+    /// 终端界面、局域网考试与 HTTP 接口都经由这里保存作答，判分因此一致
+    pub fn answer(&mut self, user_input: &[Option<String>]) -> Result<(), InvalidInput> {
+        let user_input = self.normalize_input(user_input)?;
+        self.set_user_input(user_input);
+        Ok(())
+    }
+
+    /// 题目满分
+    pub fn full_score(&self) -> u16 {
+        match self {
+            QuestionEnum::SingleSelect(q) => q.score,
+            QuestionEnum::MultiSelect(q) => q.score,
+            QuestionEnum::Judge(q) => q.score,
+            QuestionEnum::FillIn(q) => q.items.iter().map(|item| item.score).sum(),
+            QuestionEnum::Group(q) => q.questions.iter().map(QuestionEnum::full_score).sum(),
+        }
+    }
+
+    /// 标准答案的文字形式，用于直接展示答案；分隔符与语言无关
+    pub fn answer_text(&self) -> String {
+        match self {
+            QuestionEnum::SingleSelect(q) => q.answer.clone(),
+            QuestionEnum::MultiSelect(q) => q.answer.clone(),
+            QuestionEnum::Judge(q) => q.answer.clone(),
+            QuestionEnum::FillIn(q) => q
+                .items
+                .iter()
+                .map(|item| item.answer.clone())
+                .collect::<Vec<String>>()
+                .join(" / "),
+            QuestionEnum::Group(q) => q
+                .questions
+                .iter()
+                .enumerate()
+                .map(|(i, q)| format!("({}) {}", i + 1, q.answer_text()))
+                .collect::<Vec<String>>()
+                .join(" "),
+        }
+    }
+
+    /// 题目的稳定标识：对题干与选项做 FNV-1a 哈希，不包含答案与作答
+    pub fn id(&self) -> String {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        let mut feed = |s: &str| {
+            for b in s.bytes().chain(std::iter::once(0)) {
+                hash ^= b as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        };
+        self.feed_id(&mut feed);
+        format!("{hash:016x}")
+    }
+
+    fn feed_id(&self, feed: &mut impl FnMut(&str)) {
+        match self {
+            QuestionEnum::SingleSelect(q) => {
+                feed("SingleSelect");
+                feed(&q.question);
+                q.options.iter().for_each(|o| feed(o));
+            }
+            QuestionEnum::MultiSelect(q) => {
+                feed("MultiSelect");
+                feed(&q.question);
+                q.options.iter().for_each(|o| feed(o));
+            }
+            QuestionEnum::Judge(q) => {
+                feed("Judge");
+                feed(&q.question);
+            }
+            QuestionEnum::FillIn(q) => {
+                feed("FillIn");
+                feed(&q.question);
+            }
+            QuestionEnum::Group(q) => {
+                feed("Group");
+                feed(&q.question);
+                q.questions.iter().for_each(|q| q.feed_id(feed));
+            }
+        }
+    }
+}
+
+impl Question for SingleSelect {
+    fn user_input(&self) -> Option<String> {
+        self.user_input.clone()
+    }
+
+    fn answer(&self) -> String {
+        self.answer.clone()
+    }
+
+    fn question(&self) -> String {
+        self.question.clone()
+    }
+
+    fn score(&self) -> u16 {
+        self.score
+    }
+
+    fn answered(&self) -> bool {
+        self.user_input.is_some()
+    }
+}
+
+impl Question for MultiSelect {
+    fn user_input(&self) -> Option<String> {
+        self.user_input.clone()
+    }
+
+    fn answer(&self) -> String {
+        self.answer.clone()
+    }
+    fn question(&self) -> String {
+        self.question.clone()
+    }
+    fn score(&self) -> u16 {
+        self.score
+    }
+
+    fn answered(&self) -> bool {
+        self.user_input.is_some()
+    }
+}
+
+impl Question for Judge {
+    fn user_input(&self) -> Option<String> {
+        self.user_input.clone()
+    }
+
+    fn answer(&self) -> String {
+        self.answer.clone()
+    }
+    fn question(&self) -> String {
+        self.question.clone()
+    }
+    fn score(&self) -> u16 {
+        self.score
+    }
+
+    fn answered(&self) -> bool {
+        self.user_input.is_some()
+    }
+}
+
+impl Question for FillIn {
+    fn cal_score(&self) -> u16 {
+        self.items
+            .iter()
+            .map(|item| match item.user_input.clone() {
+                Some(user_input) if user_input == item.answer => item.score,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    fn user_input(&self) -> Option<String> {
+        unimplemented!("无需为填空题实现该方法")
+    }
+
+    fn answer(&self) -> String {
+        unimplemented!("无需为填空题实现该方法")
+    }
+    fn question(&self) -> String {
+        self.question.clone()
+    }
+    fn score(&self) -> u16 {
+        unimplemented!("无需为填空题实现该方法")
+    }
+    fn answered(&self) -> bool {
+        self.items.iter().all(|item| item.user_input.is_some())
+    }
+}
+
+impl FillIn {
+    fn user_input(&self) -> Vec<Option<String>> {
+        self.items
+            .iter()
+            .map(|item| item.user_input.clone())
+            .collect()
+    }
+}
+
+impl Group {
+    fn cal_score(&self) -> u16 {
+        self.questions.iter().map(QuestionEnum::cal_score).sum()
+    }
+
+    fn answered(&self) -> bool {
+        self.questions.iter().all(QuestionEnum::answered)
+    }
+
+    fn user_input(&self) -> Vec<Option<String>> {
+        self.questions
+            .iter()
+            .flat_map(QuestionEnum::user_input)
+            .collect()
+    }
+}
+
+/// 输入框的输入类型，用于校验并规范化用户输入
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InputKind {
+    /// 单选题，参数为选项个数
+    Single(usize),
+    /// 多选题，参数为选项个数
+    Multi(usize),
+    /// 判断题
+    Judge,
+    /// 填空题，不做限制
+    Text,
+}
+
+/// 作答内容不合法的原因
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputError {
+    /// 不是合法的选项字母，`range` 为合法的范围，如 `A-D`
+    InvalidOption { option: char, range: String },
+    /// 单选题选择了多个选项
+    SingleOnly { range: String },
+    /// 判断题的回答不是“是”或“否”
+    Judge,
+    /// 作答的数量多于输入框的数量
+    TooMany { expected: usize },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::InvalidOption { option, range } => {
+                write!(f, "invalid option {option}, expected {range}")
+            }
+            InputError::SingleOnly { range } => write!(f, "choose exactly one option ({range})"),
+            InputError::Judge => f.write_str("expected Yes or No"),
+            InputError::TooMany { expected } => write!(f, "at most {expected} answers expected"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// 第 `index` 个输入框的作答不合法
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidInput {
+    pub index: usize,
+    pub error: InputError,
+}
+
+impl Display for InvalidInput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "input {}: {}", self.index + 1, self.error)
+    }
+}
+
+impl std::error::Error for InvalidInput {}
+
+impl InputKind {
+    /// 校验并规范化输入：空输入视为未作答；单选为一个合法字母，多选为去重排序后的合法字母，
+    /// 判断题统一为 Yes/No
+    pub fn normalize(&self, input: &str) -> Result<Option<String>, InputError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(None);
+        }
+        match self {
+            InputKind::Single(n) => {
+                let letters = parse_letters(input, *n)?;
+                if letters.len() != 1 {
+                    return Err(InputError::SingleOnly {
+                        range: letter_range(*n),
+                    });
+                }
+                Ok(Some(letters.into_iter().collect()))
+            }
+            InputKind::Multi(n) => {
+                let mut letters = parse_letters(input, *n)?;
+                letters.sort();
+                letters.dedup();
+                Ok(Some(letters.into_iter().collect()))
+            }
+            InputKind::Judge => match input.to_lowercase().as_str() {
+                "y" | "yes" | "是" | "对" | "√" => Ok(Some("Yes".to_string())),
+                "n" | "no" | "否" | "错" | "×" => Ok(Some("No".to_string())),
+                _ => Err(InputError::Judge),
+            },
+            InputKind::Text => Ok(Some(input.to_string())),
+        }
+    }
+}
+
+/// 前 n 个选项字母的范围，如 `A-D`
+pub fn letter_range(n: usize) -> String {
+    match to_letter(n.saturating_sub(1)) {
+        Some(last) if n > 1 => format!("A-{last}"),
+        _ => "A".to_string(),
+    }
+}
+
+/// 解析选项字母，忽略空白与分隔符
+fn parse_letters(input: &str, n: usize) -> Result<Vec<char>, InputError> {
+    input
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, ',' | '，' | '、'))
+        .map(|c| {
+            let c = c.to_ascii_uppercase();
+            match to_idx(c.to_string().as_str()) {
+                Some(idx) if idx < n => Ok(c),
+                _ => Err(InputError::InvalidOption {
+                    option: c,
+                    range: letter_range(n),
+                }),
+            }
+        })
+        .collect()
+}
+
+/// 选项序号对应的字母
+pub fn to_letter(idx: usize) -> Option<char> {
+    (idx < 8).then(|| (b'A' + idx as u8) as char)
+}
+
+/// 选项字母对应的序号，不区分大小写
+pub fn to_idx(answer: &str) -> Option<usize> {
+    match answer {
+        "A" | "a" => Some(0),
+        "B" | "b" => Some(1),
+        "C" | "c" => Some(2),
+        "D" | "d" => Some(3),
+        "E" | "e" => Some(4),
+        "F" | "f" => Some(5),
+        "G" | "g" => Some(6),
+        "H" | "h" => Some(7),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::question::{
+        FillIn, FillInItem, Group, InputError, InputKind, InvalidInput, Judge, MultiSelect,
+        Question, QuestionEnum,
+    };
+
+    #[test]
+    fn test_cal_score() {
+        let multi_select = MultiSelect {
+            question: "question".to_string(),
+            options: vec!["A".to_string(), "B".to_string()],
+            answer: "AB".to_string(),
+            user_input: Some("ba".to_string()),
+            score: 1,
+            explanation: None,
+            reference: None,
+        };
+        let score = multi_select.cal_score();
+        assert_eq!(score, 1);
+    }
+
+    #[test]
+    fn test_group_user_input_and_score() {
+        let mut group = QuestionEnum::Group(Group {
+            question: "阅读材料，回答问题".to_string(),
+            questions: vec![
+                QuestionEnum::Judge(Judge {
+                    question: "太阳东升西落，对吗？（ ）".to_string(),
+                    answer: "Yes".to_string(),
+                    user_input: None,
+                    score: 1,
+                    explanation: None,
+                    reference: None,
+                }),
+                QuestionEnum::FillIn(FillIn {
+                    question: "北京奥运会和冬奥会分别于（ ）年和（ ）年举行。".to_string(),
+                    items: vec![
+                        FillInItem {
+                            answer: "2008".to_string(),
+                            user_input: None,
+                            score: 1,
+                        },
+                        FillInItem {
+                            answer: "2022".to_string(),
+                            user_input: None,
+                            score: 1,
+                        },
+                    ],
+                    explanation: None,
+                    reference: None,
+                }),
+            ],
+            explanation: Some("“双奥之城”指同时举办过夏季和冬季奥运会的城市".to_string()),
+            reference: None,
+        });
+        assert_eq!(group.input_size(), 3);
+        assert_eq!(group.answer_text(), "(1) Yes (2) 2008 / 2022");
+        assert!(group.check_blanks().is_ok());
+        group.set_user_input(vec![
+            Some("Yes".to_string()),
+            Some("2008".to_string()),
+            Some("2020".to_string()),
+        ]);
+        assert!(group.answered());
+        assert_eq!(group.cal_score(), 2);
+    }
+
+    #[test]
+    fn test_toggle_option() {
+        let mut q = QuestionEnum::MultiSelect(MultiSelect {
+            question: "下列哪些是奇数？（ ）".to_string(),
+            options: vec!["A: 1".to_string(), "B: 2".to_string(), "C: 3".to_string()],
+            answer: "AC".to_string(),
+            user_input: None,
+            score: 2,
+            explanation: None,
+            reference: None,
+        });
+        assert!(q.toggle_option(2));
+        assert!(q.toggle_option(0));
+        assert_eq!(q.user_input(), vec![Some("AC".to_string())]);
+        assert!(q.toggle_option(2));
+        assert_eq!(q.user_input(), vec![Some("A".to_string())]);
+        assert!(!q.toggle_option(3));
+        assert!(q.toggle_option(0));
+        assert_eq!(q.user_input(), vec![None]);
+    }
+
+    #[test]
+    fn test_input_kind_normalize() {
+        assert_eq!(
+            InputKind::Single(4).normalize(" b "),
+            Ok(Some("B".to_string()))
+        );
+        assert!(matches!(
+            InputKind::Single(4).normalize("Z"),
+            Err(InputError::InvalidOption { option: 'Z', .. })
+        ));
+        assert!(matches!(
+            InputKind::Single(4).normalize("AB"),
+            Err(InputError::SingleOnly { .. })
+        ));
+        assert_eq!(
+            InputKind::Multi(4).normalize("c, a, C"),
+            Ok(Some("AC".to_string()))
+        );
+        assert!(InputKind::Multi(3).normalize("AD").is_err());
+        assert_eq!(InputKind::Judge.normalize("y"), Ok(Some("Yes".to_string())));
+        assert_eq!(InputKind::Judge.normalize("maybe"), Err(InputError::Judge));
+        assert_eq!(InputKind::Text.normalize(""), Ok(None));
+    }
+
+    #[test]
+    fn test_answer() {
+        let mut q: QuestionEnum = serde_json::from_str(
+            r#"{"Group":{"question":"材料","questions":[
+                {"Judge":{"question":"对吗？（ ）","answer":"Yes","score":1}},
+                {"MultiSelect":{"question":"选择（ ）","options":["A: 1","B: 2","C: 3"],"answer":"AC","score":1}}
+            ]}}"#,
+        )
+        .unwrap();
+        let input = |s: &[&str]| s.iter().map(|s| Some(s.to_string())).collect::<Vec<_>>();
+        assert_eq!(
+            q.answer(&input(&["y", "Z"])),
+            Err(InvalidInput {
+                index: 1,
+                error: InputError::InvalidOption {
+                    option: 'Z',
+                    range: "A-C".to_string()
+                }
+            })
+        );
+        assert!(matches!(
+            q.answer(&input(&["y", "a", "b"])),
+            Err(InvalidInput {
+                error: InputError::TooMany { expected: 2 },
+                ..
+            })
+        ));
+        // 不合法时不修改原来的作答
+        assert_eq!(q.user_input(), vec![None, None]);
+        q.answer(&input(&["y", "c a"])).unwrap();
+        assert_eq!(q.user_input(), input(&["Yes", "AC"]));
+        assert_eq!(q.cal_score(), 2);
+    }
+}
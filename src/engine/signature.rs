@@ -0,0 +1,194 @@
+//! 成绩签名：用 HMAC-SHA256 对成绩文件签名，签名后内容被修改即无法通过校验。
+//!
+//! HMAC 是对称签名，签名与校验使用同一个密钥，能读到密钥的人就能伪造签名。因此密钥只能保存在
+//! 考生接触不到的机器上（`serve`、`api` 所在的监考机器），只有在那里判分并写出的成绩才签名。
+//! 校验通过只说明文件在持有密钥的一方签名之后没有被改动，不能证明考生端上报的作答、用时与审计事件
+//! 是真实的，也无法让第三方在没有密钥的情况下校验。
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+/// 签名字段名，签名覆盖对象中除它以外的全部字段
+pub const SIGNATURE: &str = "signature";
+
+const KEY_LEN: usize = 32;
+
+/// 签名密钥，只能保存在监考机器上，不能分发给考生机器
+#[derive(Clone)]
+pub struct SigningKey(Vec<u8>);
+
+/// 带签名的内容，签名与内容的字段并列
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Signed<T> {
+    #[serde(flatten)]
+    pub payload: T,
+    pub signature: String,
+}
+
+/// 读取签名密钥或校验签名失败
+#[derive(Debug)]
+pub enum SignError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// 密钥文件格式错误
+    BadKey,
+    /// 文件中没有签名
+    Unsigned,
+    /// 签名与内容不符，文件已被修改或使用了其他密钥
+    Mismatch,
+}
+
+impl Display for SignError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SignError::Io(e) => e.fmt(f),
+            SignError::Json(e) => e.fmt(f),
+            SignError::BadKey => f.write_str("malformed signing key"),
+            SignError::Unsigned => f.write_str("no signature found"),
+            SignError::Mismatch => f.write_str("signature mismatch"),
+        }
+    }
+}
+
+impl std::error::Error for SignError {}
+
+impl From<std::io::Error> for SignError {
+    fn from(e: std::io::Error) -> Self {
+        SignError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for SignError {
+    fn from(e: serde_json::Error) -> Self {
+        SignError::Json(e)
+    }
+}
+
+impl fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // 不在日志中输出密钥
+        f.write_str("SigningKey(..)")
+    }
+}
+
+impl SigningKey {
+    pub fn generate() -> Self {
+        let mut key = vec![0u8; KEY_LEN];
+        rand::rng().fill(key.as_mut_slice());
+        Self(key)
+    }
+
+    /// 读取 base64 编码的密钥文件
+    pub fn load(path: &Path) -> Result<Self, SignError> {
+        STANDARD
+            .decode(fs::read_to_string(path)?.trim())
+            .ok()
+            .filter(|key| !key.is_empty())
+            .map(Self)
+            .ok_or(SignError::BadKey)
+    }
+
+    /// 读取密钥文件，不存在时生成新的密钥并保存
+    pub fn load_or_create(path: &Path) -> Result<Self, SignError> {
+        if path.exists() {
+            return Self::load(path);
+        }
+        let key = Self::generate();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, STANDARD.encode(&key.0))?;
+        Ok(key)
+    }
+
+    /// 对序列化为 JSON 对象的内容签名
+    pub fn sign<T: Serialize>(&self, payload: T) -> Result<Signed<T>, SignError> {
+        let signature = STANDARD.encode(
+            self.mac(&serde_json::to_value(&payload)?)
+                .finalize()
+                .into_bytes(),
+        );
+        Ok(Signed { payload, signature })
+    }
+
+    /// 校验 JSON 对象中的签名
+    pub fn verify(&self, value: &Value) -> Result<(), SignError> {
+        let mut value = value.clone();
+        let signature = value
+            .as_object_mut()
+            .and_then(|object| object.remove(SIGNATURE))
+            .and_then(|signature| STANDARD.decode(signature.as_str()?).ok())
+            .ok_or(SignError::Unsigned)?;
+        self.mac(&value)
+            .verify_slice(&signature)
+            .map_err(|_| SignError::Mismatch)
+    }
+
+    fn mac(&self, value: &Value) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.0).expect("HMAC accepts any key length");
+        mac.update(canonical(value).as_bytes());
+        mac
+    }
+}
+
+/// 规范化的 JSON 文本：对象的键按字典序排列，不含空白，与文件的格式和键的顺序无关
+fn canonical(value: &Value) -> String {
+    match value {
+        Value::Object(object) => {
+            let mut entries = object.iter().collect::<Vec<_>>();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let entries = entries
+                .into_iter()
+                .map(|(k, v)| format!("{}:{}", Value::String(k.clone()), canonical(v)))
+                .collect::<Vec<_>>();
+            format!("{{{}}}", entries.join(","))
+        }
+        Value::Array(items) => {
+            let items = items.iter().map(canonical).collect::<Vec<_>>();
+            format!("[{}]", items.join(","))
+        }
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::signature::{SignError, SigningKey};
+    use serde_json::{Value, json};
+
+    #[test]
+    fn test_sign_and_verify() {
+        let key = SigningKey::generate();
+        let signed = key
+            .sign(json!({"name": "alice", "score": 2, "answers": ["B", null]}))
+            .unwrap();
+        let text = serde_json::to_string_pretty(&signed).unwrap();
+        let mut value: Value = serde_json::from_str(&text).unwrap();
+        assert!(key.verify(&value).is_ok());
+
+        // 调整格式与键的顺序不影响校验
+        let reordered: Value = serde_json::from_str(&format!(
+            r#"{{"score":2,"signature":{},"answers":["B",null],"name":"alice"}}"#,
+            value["signature"]
+        ))
+        .unwrap();
+        assert!(key.verify(&reordered).is_ok());
+
+        assert!(matches!(
+            SigningKey::generate().verify(&value),
+            Err(SignError::Mismatch)
+        ));
+        value["score"] = json!(3);
+        assert!(matches!(key.verify(&value), Err(SignError::Mismatch)));
+        value.as_object_mut().unwrap().remove("signature");
+        assert!(matches!(key.verify(&value), Err(SignError::Unsigned)));
+    }
+}
@@ -0,0 +1,494 @@
+//! 界面文字的多语言目录，目前支持简体中文与英文。
+//!
+//! 语言按命令行参数 `--lang`、配置 `"language"`、环境变量 `LANG` 的顺序确定，默认简体中文。
+//! 带参数的文字使用 `{name}` 占位，由 [`tf`] 替换。
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Display;
+use std::sync::{LazyLock, OnceLock};
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Locale {
+    #[default]
+    ZhCn,
+    En,
+}
+
+impl Locale {
+    /// 解析 `zh`、`zh-CN`、`en_US.UTF-8` 等写法，无法识别时返回 None
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.to_ascii_lowercase();
+        if value.starts_with("zh") {
+            Some(Locale::ZhCn)
+        } else if value.starts_with("en") {
+            Some(Locale::En)
+        } else {
+            None
+        }
+    }
+}
+
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+/// (键, 简体中文, 英文)
+const MESSAGES: &[(&str, &str, &str)] = &[
+    // 动作说明
+    ("action.quit", "退出", "Quit"),
+    ("action.suspend", "挂起", "Suspend"),
+    ("action.help", "显示/关闭帮助", "Toggle help"),
+    ("action.submit", "交卷", "Submit the paper"),
+    (
+        "action.toggle_explanation",
+        "显示/隐藏解析",
+        "Toggle explanation",
+    ),
+    ("action.toggle_flag", "标记/取消标记", "Flag / unflag"),
+    ("action.next_question", "下一题", "Next question"),
+    ("action.prev_question", "上一题", "Previous question"),
+    ("action.next_tab", "下一题型", "Next question type"),
+    ("action.prev_tab", "上一题型", "Previous question type"),
+    ("action.open_answer", "作答", "Answer"),
+    ("action.reveal_answer", "查看答案", "Show answer"),
+    ("action.start_jump", "输入题号跳转", "Go to question number"),
+    (
+        "action.jump_to_selected",
+        "跳转到选中的题目",
+        "Go to selected question",
+    ),
+    ("action.confirm_dialog", "确认", "Confirm"),
+    ("action.cancel_dialog", "取消/关闭", "Cancel / close"),
+    ("action.next_input", "下一个输入框", "Next input box"),
+    ("action.next_option", "下一个选项", "Next option"),
+    ("action.prev_option", "上一个选项", "Previous option"),
+    ("action.toggle_option", "选中/取消选项", "Toggle option"),
+    ("action.answer_yes", "回答“对”", "Answer Yes"),
+    ("action.answer_no", "回答“错”", "Answer No"),
+    (
+        "action.toggle_linear",
+        "切换纯文本视图",
+        "Toggle plain-text view",
+    ),
+    (
+        "action.extend_time",
+        "延长考试时间 5 分钟",
+        "Extend the exam by 5 minutes",
+    ),
+    (
+        "action.start_broadcast",
+        "向考生广播消息",
+        "Broadcast a message",
+    ),
+    ("action.submit_all", "强制收卷", "Force-submit everyone"),
+    (
+        "action.stop_exam",
+        "收卷并停止考试",
+        "Collect all papers and stop the exam",
+    ),
+    // 题型
+    ("type.single_select", "单选题", "Single choice"),
+    ("type.multi_select", "多选题", "Multiple choice"),
+    ("type.judge", "判断题", "True or false"),
+    ("type.fill_in", "填空题", "Fill in the blank"),
+    ("type.group", "综合题", "Comprehensive"),
+    // 考试
+    ("navigator.title", "答题卡", "Answer sheet"),
+    ("navigator.answered", " 已答 ", " Answered "),
+    ("navigator.flagged", " 标记", " Flagged"),
+    (
+        "exam.practice_score",
+        "练习得分是{score}，不计入考试成绩",
+        "Practice score: {score}, not counted as an exam result",
+    ),
+    (
+        "exam.final_score",
+        "您的最终得分是{score}",
+        "Your final score is {score}",
+    ),
+    ("summary.title", "交卷确认", "Confirm submission"),
+    (
+        "summary.stats",
+        "共 {total} 题，已答 {answered} 题，未答 {unanswered} 题，标记 {flagged} 题",
+        "{total} questions, {answered} answered, {unanswered} unanswered, {flagged} flagged",
+    ),
+    (
+        "summary.all_answered",
+        "全部题目均已作答",
+        "All questions are answered",
+    ),
+    ("summary.number", "第 {number} 题 ", "Question {number} "),
+    ("summary.unanswered", "未作答 ", "Unanswered "),
+    ("summary.flagged", "⚑ 已标记", "⚑ Flagged"),
+    (
+        "jump.prompt",
+        "跳转到第 {input} 题（{hint}）",
+        "Go to question {input} ({hint})",
+    ),
+    ("explanation.title", "解析", "Explanation"),
+    ("explanation.none", "暂无解析", "No explanation"),
+    (
+        "explanation.reference",
+        "参考：{reference}",
+        "Reference: {reference}",
+    ),
+    ("explanation.child", "（{number}）", "({number})"),
+    // 无障碍模式下的对错标记
+    (
+        "answer.right_option",
+        " ✓ 你的答案（正确）",
+        " ✓ Your answer (correct)",
+    ),
+    (
+        "answer.wrong_option",
+        " ✗ 你的答案（错误）",
+        " ✗ Your answer (wrong)",
+    ),
+    ("answer.correct_option", " ✓ 正确答案", " ✓ Correct answer"),
+    (
+        "answer.right",
+        "✓ 你的答案：{input}，正确",
+        "✓ Your answer: {input}, correct",
+    ),
+    (
+        "answer.wrong",
+        "✗ 你的答案：{input}，正确答案：{answer}",
+        "✗ Your answer: {input}, correct answer: {answer}",
+    ),
+    // 作答弹框
+    ("input.single", "单选 {range}", "Single {range}"),
+    ("input.multi", "多选 {range}", "Multiple {range}"),
+    ("input.judge", "判断 Y/N", "True or false Y/N"),
+    ("input.text", "填空", "Text"),
+    (
+        "input.single_only",
+        "单选题只能选择一个选项（{range}）",
+        "Choose exactly one option ({range})",
+    ),
+    (
+        "input.judge_invalid",
+        "判断题请输入 Y 或 N",
+        "Please enter Y or N",
+    ),
+    (
+        "input.invalid_option",
+        "无效选项 {option}，请输入 {range}",
+        "Invalid option {option}, please enter {range}",
+    ),
+    (
+        "input.too_many",
+        "作答数量超过输入框数量 {expected}",
+        "At most {expected} answers are expected",
+    ),
+    ("judge.yes", "对({key})", "Yes({key})"),
+    ("judge.no", "错({key})", "No({key})"),
+    // 底部与弹框中的操作提示
+    ("footer.entry", "{key} {label}", "{key} to {label}"),
+    ("footer.change_tab", "切换题型", "change tab"),
+    ("footer.write_answer", "作答", "write answer"),
+    ("footer.flag", "标记", "flag"),
+    ("footer.jump", "跳转", "jump"),
+    ("footer.submit", "交卷", "submit"),
+    ("footer.submit_answer", "提交", "submit"),
+    ("footer.help", "帮助", "help"),
+    ("footer.quit", "退出", "quit"),
+    ("footer.toggle_explanation", "解析", "toggle explanation"),
+    ("footer.select", "选择", "select"),
+    ("footer.confirm", "确认", "confirm"),
+    ("footer.cancel", "取消", "cancel"),
+    ("footer.clear", "清空", "clear"),
+    ("footer.close", "关闭", "close"),
+    ("footer.exit", "退出", "exit"),
+    ("footer.switch", "切换输入框", "switch"),
+    ("footer.answer_yes", "回答“对”", "answer Yes"),
+    ("footer.answer_no", "回答“错”", "answer No"),
+    ("footer.show_answer", "查看答案", "show answer"),
+    ("footer.accept", "接受建议评分", "accept"),
+    ("footer.choose_option", "A-H 选择", "A-H to choose"),
+    ("footer.toggle_option", "A-H 选中/取消", "A-H to toggle"),
+    ("footer.rate", "0-5 评分", "0-5 to rate recall"),
+    ("footer.extend", "延长时间", "extend"),
+    ("footer.broadcast", "广播", "broadcast"),
+    ("footer.submit_all", "收卷", "force-submit"),
+    (
+        "footer.rate_hint",
+        "0-5 评分（0 完全忘记，5 完全记得）",
+        "0-5 to rate recall (0 forgot, 5 perfect)",
+    ),
+    // 帮助
+    ("help.title", "快捷键", "Keybindings"),
+    ("help.choose_option", "选择选项", "Choose an option"),
+    ("help.rate", "复习模式下评分", "Rate recall in study mode"),
+    ("help.toggle_option", "选择题选中选项", "Toggle an option"),
+    ("help.move_cursor", "移动光标", "Move the cursor"),
+    ("help.move_word", "按词移动光标", "Move the cursor by word"),
+    ("help.delete_char", "删除字符", "Delete a character"),
+    (
+        "help.delete_line_word",
+        "删除到行首 / 删除前一个词",
+        "Delete to line start / previous word",
+    ),
+    ("help.type_number", "输入题号", "Type the question number"),
+    ("help.delete", "删除", "Delete"),
+    ("help.type_message", "输入消息", "Type the message"),
+    ("help.type_password", "输入口令", "Type the password"),
+    // 复习
+    (
+        "study.title",
+        "每日复习 · 已复习 {reviewed} 题 · 待复习 {remaining} 题",
+        "Daily review · {reviewed} reviewed · {remaining} remaining",
+    ),
+    (
+        "study.done",
+        "今日复习已完成",
+        "All reviews are done for today",
+    ),
+    ("study.answer", "答案：{answer}", "Answer: {answer}"),
+    (
+        "study.suggested",
+        "自动判分建议评分：{quality}",
+        "Suggested rating: {quality}",
+    ),
+    ("timer.remaining", "剩余时间：{time}", "Time left: {time}"),
+    (
+        "timer.question",
+        "本题剩余 {seconds} 秒",
+        "{seconds}s left for this question",
+    ),
+    ("timer.spent", "用时 {time}", "Time spent {time}"),
+    ("timer.timed_out", "（已超时）", "(timed out)"),
+    // 局域网考试
+    (
+        "serve.listening",
+        "考试「{name}」在 {addr} 上等待考生加入，{start} 开始，{deadline} 截止",
+        "Exam \"{name}\" is listening on {addr}, starting at {start} and ending at {deadline}",
+    ),
+    ("serve.joined", "{name} 加入考试", "{name} joined"),
+    ("serve.left", "{name} 断开连接", "{name} disconnected"),
+    (
+        "serve.graded",
+        "{name} 已交卷，得分 {score}",
+        "{name} submitted, score {score}",
+    ),
+    (
+        "serve.name_taken",
+        "考生 {name} 已在其他终端作答",
+        "Candidate {name} is already connected",
+    ),
+    (
+        "serve.token_mismatch",
+        "考生 {name} 已加入考试，断线重连请使用原来的考生端",
+        "Candidate {name} has already joined; reconnect from the original computer",
+    ),
+    (
+        "serve.submitted",
+        "已交卷，不能再修改答案",
+        "The paper is already submitted",
+    ),
+    ("serve.time_up", "考试时间已到", "Time is up"),
+    (
+        "serve.extended",
+        "考试延长至 {deadline}",
+        "The exam is extended to {deadline}",
+    ),
+    ("serve.broadcast", "广播：{message}", "Broadcast: {message}"),
+    ("serve.invalid_question", "题目不存在", "No such question"),
+    (
+        "serve.invalid_answer",
+        "作答无效：{error}",
+        "Invalid answer: {error}",
+    ),
+    (
+        "serve.focus_lost",
+        "{name} 切出了考试窗口",
+        "{name} left the exam window",
+    ),
+    (
+        "serve.suspend_attempt",
+        "{name} 尝试挂起考试",
+        "{name} tried to suspend the exam",
+    ),
+    // 审计
+    (
+        "audit.confirm_suspend",
+        "考试期间挂起会被记录，确定要挂起吗？",
+        "Suspending during the exam is recorded. Suspend anyway?",
+    ),
+    (
+        "audit.suspend_forbidden",
+        "考试期间禁止挂起，本次尝试已被记录",
+        "Suspending is not allowed during the exam, the attempt has been recorded",
+    ),
+    // HTTP 接口
+    (
+        "api.listening",
+        "HTTP 接口在 {addr} 上提供服务",
+        "HTTP API is listening on {addr}",
+    ),
+    ("api.not_found", "考试或试卷不存在", "No such exam or paper"),
+    (
+        "api.not_submitted",
+        "试卷尚未交卷",
+        "The paper is not submitted yet",
+    ),
+    // 答案密钥
+    (
+        "seal.prompt",
+        "请输入答案密钥的口令：",
+        "Answer key password: ",
+    ),
+    (
+        "seal.cancelled",
+        "已取消输入口令",
+        "Password entry cancelled",
+    ),
+    (
+        "seal.empty_password",
+        "口令不能为空",
+        "The password must not be empty",
+    ),
+    (
+        "seal.done",
+        "不含答案的题库已写入 {bank}，答案密钥已写入 {key}",
+        "Wrote the bank without answers to {bank} and the answer key to {key}",
+    ),
+    (
+        "unlock.prompt",
+        "已交卷，请监考老师输入口令判分：{input}  {hint}",
+        "Submitted. Proctor, enter the answer key password: {input}  {hint}",
+    ),
+    ("unlock.wrong_password", "口令错误", "Wrong password"),
+    (
+        "unlock.unavailable",
+        "题库的答案已加密，无法使用练习与复习模式",
+        "The answers are sealed, practice and study modes are unavailable",
+    ),
+    // 成绩签名
+    (
+        "verify.ok",
+        "{file} 的签名有效，内容未被修改",
+        "{file}: the signature is valid, the content is unaltered",
+    ),
+    ("verify.unsigned", "{file} 没有签名", "{file} is not signed"),
+    (
+        "verify.mismatch",
+        "{file} 的签名无效：内容已被修改，或不是用该密钥签名的",
+        "{file}: invalid signature, the content was altered or signed with another key",
+    ),
+    // 监考面板
+    ("proctor.title", "监考：{name}", "Proctoring: {name}"),
+    (
+        "proctor.stats",
+        "考生 {total} 人，在线 {online} 人，已交卷 {submitted} 人",
+        "{total} candidates, {online} online, {submitted} submitted",
+    ),
+    ("proctor.name", "考生", "Candidate"),
+    ("proctor.status", "状态", "Status"),
+    ("proctor.focus_lost", "切出", "Left"),
+    ("proctor.remaining", "剩余时间", "Remaining"),
+    ("proctor.online", "在线", "Online"),
+    ("proctor.offline", "离线", "Offline"),
+    ("proctor.score", "得分 {score}", "Score {score}"),
+    ("proctor.events", "事件", "Events"),
+    (
+        "proctor.broadcast",
+        "广播消息：{input}  {hint}",
+        "Broadcast: {input}  {hint}",
+    ),
+    (
+        "proctor.confirm_submit_all",
+        "确定为所有未交卷的考生收卷吗？",
+        "Force-submit every candidate who has not submitted?",
+    ),
+    (
+        "proctor.confirm_stop",
+        "仍有 {online} 名考生在线，确定收卷并停止考试吗？",
+        "{online} candidates are still online. Collect all papers and stop the exam?",
+    ),
+    (
+        "join.waiting",
+        "考试将于 {start} 开始，请稍候……",
+        "The exam starts at {start}, please wait...",
+    ),
+    (
+        "join.disconnected",
+        "与考试服务端的连接已断开",
+        "Lost connection to the exam server",
+    ),
+];
+
+static CATALOG: LazyLock<HashMap<&'static str, (&'static str, &'static str)>> =
+    LazyLock::new(|| {
+        MESSAGES
+            .iter()
+            .map(|(key, zh, en)| (*key, (*zh, *en)))
+            .collect()
+    });
+
+/// 依次按命令行参数、配置、`LANG` 环境变量确定语言，只有第一次调用生效
+pub fn init(cli: Option<&str>, config: Option<&str>) {
+    let lang = env::var("LANG").ok();
+    let locale = [cli, config, lang.as_deref()]
+        .into_iter()
+        .flatten()
+        .find_map(Locale::parse)
+        .unwrap_or_default();
+    let _ = LOCALE.set(locale);
+}
+
+pub fn locale() -> Locale {
+    LOCALE.get().copied().unwrap_or_default()
+}
+
+fn lookup(locale: Locale, key: &str) -> &'static str {
+    match CATALOG.get(key) {
+        Some((zh, _)) if locale == Locale::ZhCn => zh,
+        Some((_, en)) => en,
+        None => {
+            tracing::error!("Missing message: {key}");
+            ""
+        }
+    }
+}
+
+/// 当前语言下的文字
+pub fn t(key: &str) -> &'static str {
+    lookup(locale(), key)
+}
+
+/// 当前语言下的文字，并替换 `{name}` 占位符
+pub fn tf(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut message = t(key).to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    message
+}
+
+#[cfg(test)]
+mod test {
+    use crate::i18n::{Locale, MESSAGES, lookup};
+    use regex::Regex;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_catalog() {
+        let placeholder = Regex::new(r"\{\w+}").unwrap();
+        let mut keys = HashSet::new();
+        for (key, zh, en) in MESSAGES {
+            assert!(keys.insert(key), "duplicate key {key}");
+            let zh_args = placeholder
+                .find_iter(zh)
+                .map(|m| m.as_str())
+                .collect::<HashSet<_>>();
+            let en_args = placeholder
+                .find_iter(en)
+                .map(|m| m.as_str())
+                .collect::<HashSet<_>>();
+            assert_eq!(zh_args, en_args, "placeholders differ in {key}");
+        }
+        assert_eq!(lookup(Locale::En, "type.judge"), "True or false");
+        assert_eq!(Locale::parse("en_US.UTF-8"), Some(Locale::En));
+        assert_eq!(Locale::parse("zh-CN"), Some(Locale::ZhCn));
+        assert_eq!(Locale::parse("C"), None);
+    }
+}
@@ -0,0 +1,3 @@
+//! 考试引擎库，供终端界面以及其他工具嵌入使用，见 [`engine`]。
+
+pub mod engine;
@@ -1,15 +1,35 @@
 use crate::app::App;
+use crate::components::examination::answer_key_path;
+use crate::config::{Config, get_config_dir};
+use crate::server::ServeOptions;
+use crate::server::client::{self, Connection};
+use crate::server::protocol::ServerMessage;
+use chrono::{Local, NaiveTime};
 use clap::Parser;
-use cli::Cli;
+use cli::{Cli, Command};
 use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+use examination::engine::{self, AnswerKey, KeyError, SignError, SigningKey, key};
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 
 mod action;
+mod api;
 mod app;
 mod cli;
 mod components;
 mod config;
 mod errors;
+#[cfg(test)]
+#[path = "engine/fixture.rs"]
+mod fixture;
+mod i18n;
 mod logging;
+mod server;
+mod theme;
 mod tui;
 
 #[tokio::main]
@@ -18,7 +38,208 @@ async fn main() -> Result<()> {
     crate::logging::init()?;
 
     let args = Cli::parse();
-    let mut app = App::new(args.tick_rate, args.frame_rate)?;
+    let config = App::load_config(args.accessible, args.lang.as_deref())?;
+    let mut app = match args.command {
+        Some(Command::Serve {
+            addr,
+            shuffle,
+            start,
+            headless,
+            password,
+        }) => {
+            let key = answer_key(&config, password)?;
+            let options = ServeOptions {
+                shuffle,
+                start: start.map(today_at),
+                results_dir: config.config.data_dir.join("results"),
+                headless,
+                signing_key: Some(SigningKey::load_or_create(&signing_key_path())?),
+            };
+            let (server, listener) = server::bind(&config, &addr, options, key.as_ref()).await?;
+            if headless {
+                return server.run(listener).await;
+            }
+            server.clone().spawn(listener);
+            App::proctor(config, args.tick_rate, args.frame_rate, server)
+        }
+        Some(Command::Api { addr, password }) => {
+            let key = answer_key(&config, password)?;
+            let signing_key = SigningKey::load_or_create(&signing_key_path())?;
+            return api::serve(&config, &addr, key.as_ref(), signing_key).await;
+        }
+        Some(Command::Seal { source, password }) => return seal(&config, &source, password),
+        Some(Command::Verify { file, key }) => {
+            return verify(&file, &key.unwrap_or_else(signing_key_path));
+        }
+        Some(Command::Join { addr, name }) => {
+            let tokens = config.config.data_dir.join("tokens.json");
+            let token = client::load_token(&tokens, &addr, &name);
+            let mut connection = Connection::join(&addr, &name, token).await?;
+            // 等待服务端发放试卷
+            loop {
+                match connection.recv().await? {
+                    Some(ServerMessage::Token(token)) => {
+                        client::save_token(&tokens, &addr, &name, &token)?
+                    }
+                    Some(ServerMessage::Waiting { start }) => println!(
+                        "{}",
+                        i18n::tf("join.waiting", &[("start", &start.format("%H:%M"))])
+                    ),
+                    Some(ServerMessage::Paper {
+                        exam,
+                        paper,
+                        deadline,
+                    }) => {
+                        break App::remote(
+                            config,
+                            args.tick_rate,
+                            args.frame_rate,
+                            connection,
+                            exam,
+                            paper,
+                            deadline,
+                        );
+                    }
+                    Some(ServerMessage::Result { score, .. }) => {
+                        println!("{}", i18n::tf("exam.final_score", &[("score", &score)]));
+                        return Ok(());
+                    }
+                    Some(ServerMessage::Broadcast(msg)) => println!("{msg}"),
+                    // 试卷中会带上最新的截止时间
+                    Some(ServerMessage::Deadline(_)) => {}
+                    Some(ServerMessage::Error(msg)) => return Err(eyre!(msg)),
+                    None => return Err(eyre!(i18n::t("join.disconnected"))),
+                }
+            }
+        }
+        None => App::new(
+            config,
+            args.tick_rate,
+            args.frame_rate,
+            args.practice,
+            args.study,
+        )?,
+    };
     app.run().await?;
     Ok(())
 }
+
+/// 拆分题库：不含答案的题库与答案密钥写入数据目录
+fn seal(config: &Config, source: &Path, password: Option<String>) -> Result<()> {
+    let bank = engine::load_bank(source)?;
+    let mut password = password.map_or_else(read_password, Ok)?;
+    if password.is_empty() {
+        return Err(eyre!(i18n::t("seal.empty_password")));
+    }
+    let sealed = key::seal_bank(&bank, &password);
+    wipe(&mut password);
+    let (redacted, sealed) = sealed?;
+    fs::create_dir_all(&config.config.data_dir)?;
+    let bank_path = config.config.data_dir.join("question.json");
+    let key_path = answer_key_path(config);
+    fs::write(&bank_path, serde_json::to_vec_pretty(&redacted)?)?;
+    fs::write(&key_path, sealed)?;
+    println!(
+        "{}",
+        i18n::tf(
+            "seal.done",
+            &[("bank", &bank_path.display()), ("key", &key_path.display())]
+        )
+    );
+    Ok(())
+}
+
+/// 数据目录下有答案密钥时，用口令解锁
+fn answer_key(config: &Config, password: Option<String>) -> Result<Option<AnswerKey>> {
+    let path = answer_key_path(config);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let mut password = password.map_or_else(read_password, Ok)?;
+    let key = AnswerKey::load(&path, &password);
+    wipe(&mut password);
+    match key {
+        Ok(key) => Ok(Some(key)),
+        Err(KeyError::Locked) => Err(eyre!(i18n::t("unlock.wrong_password"))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 成绩签名密钥，保存在配置目录而不是存放成绩的数据目录
+fn signing_key_path() -> PathBuf {
+    get_config_dir().join("signing.key")
+}
+
+/// 校验成绩文件的签名
+fn verify(file: &Path, key: &Path) -> Result<()> {
+    let value = serde_json::from_slice(&fs::read(file)?)?;
+    let file = file.display();
+    match SigningKey::load(key)?.verify(&value) {
+        Ok(()) => {
+            println!("{}", i18n::tf("verify.ok", &[("file", &file)]));
+            Ok(())
+        }
+        Err(SignError::Unsigned) => Err(eyre!(i18n::tf("verify.unsigned", &[("file", &file)]))),
+        Err(SignError::Mismatch) => Err(eyre!(i18n::tf("verify.mismatch", &[("file", &file)]))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 在控制台读取一行口令，终端中输入不回显
+fn read_password() -> Result<String> {
+    print!("{}", i18n::t("seal.prompt"));
+    io::stdout().flush()?;
+    // 从管道读取时按行读取
+    if !io::stdin().is_terminal() {
+        let mut line = String::new();
+        io::stdin().read_line(&mut line)?;
+        let password = line.trim_end_matches(['\r', '\n']).to_string();
+        wipe(&mut line);
+        return Ok(password);
+    }
+    terminal::enable_raw_mode()?;
+    let password = read_hidden();
+    terminal::disable_raw_mode()?;
+    println!();
+    password
+}
+
+/// 在原始模式下逐键读取口令，回车结束，ctrl-c 取消
+fn read_hidden() -> Result<String> {
+    let mut password = String::new();
+    loop {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => return Ok(password),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                wipe(&mut password);
+                return Err(eyre!(i18n::t("seal.cancelled")));
+            }
+            KeyCode::Char(c) => password.push(c),
+            KeyCode::Backspace => {
+                password.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 用零覆盖口令所在的内存后清空，避免口令留在内存中
+fn wipe(buffer: &mut String) {
+    let zeros = "\0".repeat(buffer.len());
+    buffer.replace_range(.., &zeros);
+    buffer.clear();
+}
+
+/// 今天的指定时刻
+fn today_at(time: NaiveTime) -> chrono::DateTime<Local> {
+    Local::now()
+        .with_time(time)
+        .single()
+        .unwrap_or_else(Local::now)
+}
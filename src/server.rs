@@ -0,0 +1,782 @@
+//! 局域网考试服务端：为每位连接的考生发放试卷，实时接收作答内容，
+//! 按统一的开始与截止时间收卷，在服务端判分并集中保存成绩。
+//!
+//! 发给考生的试卷不含答案与解析，交卷后再发送带答案的试卷用于查看解析。
+
+pub mod client;
+pub mod protocol;
+
+use crate::components::examination::{
+    self, Examination, ExaminationConfig, QuestionEnum, QuestionType,
+};
+use crate::config::Config;
+use crate::i18n;
+use crate::server::protocol::{ClientMessage, Paper, ServerMessage};
+use ::examination::engine::paper::{score, without_answers};
+use ::examination::engine::{AnswerKey, AuditEvent, AuditKind, AuditLog, QuestionTime, SigningKey};
+use chrono::{DateTime, Local, TimeDelta};
+use color_eyre::Result;
+use linked_hash_map::LinkedHashMap;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::io::BufReader;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc::{self, UnboundedSender};
+use tracing::{error, info};
+
+#[derive(Clone, Debug, Default)]
+pub struct ServeOptions {
+    /// 每位考生单独抽题，否则所有考生使用同一份试卷
+    pub shuffle: bool,
+    /// 统一的开始时间，默认为启动服务的时间
+    pub start: Option<DateTime<Local>>,
+    /// 成绩文件所在的目录
+    pub results_dir: PathBuf,
+    /// 不显示监考面板，事件直接输出到控制台
+    pub headless: bool,
+    /// 成绩文件的签名密钥，为 None 时不签名
+    pub signing_key: Option<SigningKey>,
+}
+
+struct Candidate {
+    /// 带答案的完整试卷
+    paper: Paper,
+    score: Option<u16>,
+    submitted_at: Option<DateTime<Local>>,
+    /// 考生端上报的审计事件
+    audit: AuditLog,
+    /// 在线时发往该考生的消息
+    tx: Option<UnboundedSender<ServerMessage>>,
+    /// 首次加入时发放的重连凭证，断线重连时必须出示
+    token: String,
+}
+
+/// 成绩文件的内容
+#[derive(Serialize)]
+struct Results<'a> {
+    exam: &'a str,
+    start: DateTime<Local>,
+    results: Vec<CandidateResult<'a>>,
+}
+
+/// 成绩文件中的一条记录
+#[derive(Serialize)]
+struct CandidateResult<'a> {
+    name: &'a str,
+    score: u16,
+    submitted_at: DateTime<Local>,
+    paper: &'a Paper,
+    time_spent: Vec<QuestionTime>,
+    audit: &'a AuditLog,
+}
+
+struct State {
+    candidates: LinkedHashMap<String, Candidate>,
+    deadline: DateTime<Local>,
+    /// 成绩的版本，每次保存时递增
+    revision: u64,
+}
+
+/// 监考面板中一位考生的状态
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CandidateStatus {
+    pub name: String,
+    pub online: bool,
+    /// 各题型已答题数与总题数
+    pub answered: Vec<(QuestionType, usize, usize)>,
+    pub score: Option<u16>,
+    /// 切出考试窗口的次数
+    pub focus_lost: usize,
+    /// 距截止时间的剩余时间，已包含延长的时间；交卷后为空
+    pub remaining: Option<TimeDelta>,
+}
+
+pub struct Server {
+    exam: ExaminationConfig,
+    bank: HashMap<QuestionType, Vec<QuestionEnum>>,
+    /// 不单独抽题时所有考生共用的试卷
+    shared: Paper,
+    options: ServeOptions,
+    start: DateTime<Local>,
+    state: Mutex<State>,
+    /// 考生加入、交卷等事件，最新的在最后
+    events: Mutex<Vec<String>>,
+    /// 已写入成绩文件的版本，较旧的内容不会覆盖较新的内容
+    written: Mutex<u64>,
+}
+
+/// 读取考试配置与题库并监听指定地址，之后由 [`Server::run`] 提供考试服务
+pub async fn bind(
+    config: &Config,
+    addr: &str,
+    options: ServeOptions,
+    key: Option<&AnswerKey>,
+) -> Result<(Arc<Server>, TcpListener)> {
+    let exam = Examination::load(config.clone());
+    let mut bank = examination::load_bank(config);
+    // 题库不含答案时换回答案，由服务端判分
+    if let Some(key) = key {
+        bank = key.restore_bank(&bank);
+    }
+    let server = Arc::new(Server::new(exam, bank, options));
+    let listener = TcpListener::bind(addr).await?;
+    server.event(i18n::tf(
+        "serve.listening",
+        &[
+            ("name", &server.exam.name()),
+            ("addr", &listener.local_addr()?),
+            ("start", &server.start.format("%H:%M:%S")),
+            ("deadline", &server.deadline().format("%H:%M:%S")),
+        ],
+    ));
+    Ok((server, listener))
+}
+
+fn duration_until(time: DateTime<Local>) -> std::time::Duration {
+    (time - Local::now()).to_std().unwrap_or_default()
+}
+
+impl Server {
+    pub fn new(
+        mut exam: ExaminationConfig,
+        bank: HashMap<QuestionType, Vec<QuestionEnum>>,
+        options: ServeOptions,
+    ) -> Self {
+        // 服务端统一判分，考生端不使用练习模式
+        exam.set_practice(false);
+        let start = options.start.unwrap_or_else(Local::now);
+        let deadline = start + TimeDelta::seconds(exam.duration() as i64);
+        let shared = QuestionEnum::choose(&bank, &exam).into_iter().collect();
+        Self {
+            exam,
+            bank,
+            shared,
+            options,
+            start,
+            state: Mutex::new(State {
+                candidates: LinkedHashMap::new(),
+                deadline,
+                revision: 0,
+            }),
+            events: Mutex::new(vec![]),
+            written: Mutex::new(0),
+        }
+    }
+
+    pub fn exam(&self) -> &ExaminationConfig {
+        &self.exam
+    }
+
+    pub fn deadline(&self) -> DateTime<Local> {
+        self.state.lock().unwrap().deadline
+    }
+
+    /// 记录事件，不显示监考面板时直接输出到控制台
+    fn event(&self, message: String) {
+        info!("{message}");
+        if self.options.headless {
+            println!("{message}");
+        }
+        let message = format!("{} {message}", Local::now().format("%H:%M:%S"));
+        self.events.lock().unwrap().push(message);
+    }
+
+    /// 最近的若干条事件
+    pub fn recent_events(&self, n: usize) -> Vec<String> {
+        let events = self.events.lock().unwrap();
+        events[events.len().saturating_sub(n)..].to_vec()
+    }
+
+    /// 所有考生的作答进度，按加入顺序排列
+    pub fn snapshot(&self) -> Vec<CandidateStatus> {
+        let state = self.state.lock().unwrap();
+        let remaining = (state.deadline - Local::now()).max(TimeDelta::zero());
+        state
+            .candidates
+            .iter()
+            .map(|(name, c)| CandidateStatus {
+                name: name.clone(),
+                online: c.tx.is_some(),
+                answered: c
+                    .paper
+                    .iter()
+                    .map(|(t, qs)| (*t, qs.iter().filter(|q| q.answered()).count(), qs.len()))
+                    .collect(),
+                score: c.score,
+                focus_lost: c.audit.focus_lost(),
+                remaining: c.score.is_none().then_some(remaining),
+            })
+            .collect()
+    }
+
+    /// 延长考试时间并通知未交卷的考生
+    pub fn extend(&self, minutes: i64) {
+        let deadline = {
+            let mut state = self.state.lock().unwrap();
+            state.deadline += TimeDelta::minutes(minutes);
+            for candidate in state.candidates.values().filter(|c| c.score.is_none()) {
+                if let Some(tx) = &candidate.tx {
+                    let _ = tx.send(ServerMessage::Deadline(state.deadline));
+                }
+            }
+            state.deadline
+        };
+        self.event(i18n::tf(
+            "serve.extended",
+            &[("deadline", &deadline.format("%H:%M:%S"))],
+        ));
+    }
+
+    /// 向所有在线考生广播消息
+    pub fn broadcast(&self, message: &str) {
+        for candidate in self.state.lock().unwrap().candidates.values() {
+            if let Some(tx) = &candidate.tx {
+                let _ = tx.send(ServerMessage::Broadcast(message.to_string()));
+            }
+        }
+        self.event(i18n::tf("serve.broadcast", &[("message", &message)]));
+    }
+
+    /// 为所有未交卷的考生收卷
+    pub fn submit_all(&self) {
+        let graded = {
+            let mut state = self.state.lock().unwrap();
+            let mut graded = vec![];
+            for (name, candidate) in state.candidates.iter_mut() {
+                if candidate.score.is_none() {
+                    let (score, result) = Self::grade(candidate);
+                    if let Some(tx) = &candidate.tx {
+                        let _ = tx.send(result);
+                    }
+                    graded.push((name.clone(), score));
+                }
+            }
+            graded
+        };
+        self.save();
+        for (name, score) in graded {
+            self.graded(&name, score);
+        }
+    }
+
+    /// 接受考生连接，直到监听出错
+    pub async fn run(self: Arc<Self>, listener: TcpListener) -> Result<()> {
+        tokio::spawn(self.clone().collect_at_deadline());
+        loop {
+            let (stream, addr) = listener.accept().await?;
+            info!("Connection from {addr}");
+            let server = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = server.handle(stream).await {
+                    error!("Connection from {addr} failed: {e}");
+                }
+            });
+        }
+    }
+
+    /// 在后台提供考试服务，供监考面板使用
+    pub fn spawn(self: Arc<Self>, listener: TcpListener) {
+        tokio::spawn(async move {
+            if let Err(e) = self.run(listener).await {
+                error!("Server stopped: {e}");
+            }
+        });
+    }
+
+    fn new_paper(&self) -> Paper {
+        if self.options.shuffle {
+            QuestionEnum::choose(&self.bank, &self.exam)
+                .into_iter()
+                .collect()
+        } else {
+            self.shared.clone()
+        }
+    }
+
+    async fn handle(self: Arc<Self>, stream: TcpStream) -> Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(reader);
+        let Some(ClientMessage::Hello { name, token }) = protocol::recv(&mut reader).await? else {
+            return Ok(());
+        };
+        let (tx, mut rx) = mpsc::unbounded_channel::<ServerMessage>();
+        tokio::spawn(async move {
+            while let Some(message) = rx.recv().await {
+                if protocol::send(&mut writer, &message).await.is_err() {
+                    break;
+                }
+            }
+        });
+        match self.register(&name, token.as_deref(), tx.clone()) {
+            Ok(token) => tx.send(ServerMessage::Token(token))?,
+            Err(message) => {
+                tx.send(ServerMessage::Error(message))?;
+                return Ok(());
+            }
+        }
+        if Local::now() < self.start {
+            tx.send(ServerMessage::Waiting { start: self.start })?;
+            tokio::time::sleep(duration_until(self.start)).await;
+        }
+        tx.send(self.welcome(&name))?;
+        while let Ok(Some(message)) = protocol::recv(&mut reader).await {
+            if let Some(reply) = self.receive(&name, message) {
+                tx.send(reply)?;
+            }
+        }
+        self.disconnect(&name);
+        Ok(())
+    }
+
+    /// 登记考生并返回重连凭证；同名考生在线时拒绝加入，离线时需出示凭证才能断线重连
+    fn register(
+        &self,
+        name: &str,
+        token: Option<&str>,
+        tx: UnboundedSender<ServerMessage>,
+    ) -> Result<String, String> {
+        let mut state = self.state.lock().unwrap();
+        let token = match state.candidates.get_mut(name) {
+            Some(candidate) if candidate.tx.is_some() => {
+                return Err(i18n::tf("serve.name_taken", &[("name", &name)]));
+            }
+            Some(candidate) if token != Some(candidate.token.as_str()) => {
+                return Err(i18n::tf("serve.token_mismatch", &[("name", &name)]));
+            }
+            Some(candidate) => {
+                candidate.tx = Some(tx);
+                candidate.token.clone()
+            }
+            None => {
+                let token = format!("{:032x}", rand::random::<u128>());
+                let candidate = Candidate {
+                    paper: self.new_paper(),
+                    score: None,
+                    submitted_at: None,
+                    audit: AuditLog::default(),
+                    tx: Some(tx),
+                    token: token.clone(),
+                };
+                state.candidates.insert(name.to_string(), candidate);
+                token
+            }
+        };
+        drop(state);
+        self.event(i18n::tf("serve.joined", &[("name", &name)]));
+        Ok(token)
+    }
+
+    fn disconnect(&self, name: &str) {
+        if let Some(candidate) = self.state.lock().unwrap().candidates.get_mut(name) {
+            candidate.tx = None;
+        }
+        self.event(i18n::tf("serve.left", &[("name", &name)]));
+    }
+
+    /// 开考时发给考生的消息，已交卷的考生直接收到成绩
+    fn welcome(&self, name: &str) -> ServerMessage {
+        let state = self.state.lock().unwrap();
+        let candidate = &state.candidates[name];
+        match candidate.score {
+            Some(score) => ServerMessage::Result {
+                score,
+                paper: candidate.paper.clone(),
+            },
+            None => ServerMessage::Paper {
+                exam: self.exam.clone(),
+                paper: without_answers(&candidate.paper),
+                deadline: state.deadline,
+            },
+        }
+    }
+
+    fn receive(&self, name: &str, message: ClientMessage) -> Option<ServerMessage> {
+        // 交卷后仍会收到审计事件，不回复
+        if let ClientMessage::Audit(event) = message {
+            self.audit(name, event);
+            return None;
+        }
+        let submit = message == ClientMessage::Submit;
+        let reply = self.apply(name, message);
+        if let (true, Some(ServerMessage::Result { score, .. })) = (submit, &reply) {
+            self.save();
+            self.graded(name, *score);
+        }
+        reply
+    }
+
+    /// 记录审计事件，切出窗口与尝试挂起时提醒监考老师
+    fn audit(&self, name: &str, event: AuditEvent) {
+        let notice = match event.kind {
+            AuditKind::FocusLost => Some("serve.focus_lost"),
+            AuditKind::SuspendAttempt => Some("serve.suspend_attempt"),
+            _ => None,
+        };
+        let mut state = self.state.lock().unwrap();
+        let Some(candidate) = state.candidates.get_mut(name) else {
+            return;
+        };
+        candidate.audit.push(event);
+        let submitted = candidate.score.is_some();
+        drop(state);
+        // 交卷后的事件补充到成绩文件中
+        if submitted {
+            self.save();
+        }
+        if let Some(key) = notice {
+            self.event(i18n::tf(key, &[("name", &name)]));
+        }
+    }
+
+    fn apply(&self, name: &str, message: ClientMessage) -> Option<ServerMessage> {
+        let mut state = self.state.lock().unwrap();
+        let deadline = state.deadline;
+        let candidate = state.candidates.get_mut(name)?;
+        if candidate.score.is_some() {
+            return Some(ServerMessage::Error(i18n::t("serve.submitted").to_string()));
+        }
+        match message {
+            ClientMessage::Hello { .. } => None,
+            ClientMessage::Answer { .. } if Local::now() > deadline => {
+                Some(ServerMessage::Error(i18n::t("serve.time_up").to_string()))
+            }
+            ClientMessage::Answer {
+                question_type,
+                index,
+                user_input,
+            } => {
+                let question = candidate
+                    .paper
+                    .iter_mut()
+                    .find(|(t, _)| *t == question_type)
+                    .and_then(|(_, questions)| questions.get_mut(index));
+                match question.map(|q| q.answer(&user_input)) {
+                    Some(Ok(())) => None,
+                    Some(Err(e)) => Some(ServerMessage::Error(i18n::tf(
+                        "serve.invalid_answer",
+                        &[("error", &examination::input_error_text(&e.error))],
+                    ))),
+                    None => Some(ServerMessage::Error(
+                        i18n::t("serve.invalid_question").to_string(),
+                    )),
+                }
+            }
+            ClientMessage::Submit => Some(Self::grade(candidate).1),
+            ClientMessage::Audit(_) => None,
+        }
+    }
+
+    fn grade(candidate: &mut Candidate) -> (u16, ServerMessage) {
+        let score = score(&candidate.paper);
+        candidate.score = Some(score);
+        candidate.submitted_at = Some(Local::now());
+        let result = ServerMessage::Result {
+            score,
+            paper: candidate.paper.clone(),
+        };
+        (score, result)
+    }
+
+    fn graded(&self, name: &str, score: u16) {
+        self.event(i18n::tf(
+            "serve.graded",
+            &[("name", &name), ("score", &score)],
+        ));
+    }
+
+    /// 到截止时间后为所有未交卷的考生收卷
+    async fn collect_at_deadline(self: Arc<Self>) {
+        loop {
+            let deadline = self.deadline();
+            tokio::time::sleep(duration_until(deadline)).await;
+            // 等待期间截止时间可能被延长
+            if Local::now() < self.deadline() {
+                continue;
+            }
+            self.submit_all();
+            return;
+        }
+    }
+
+    fn results_path(&self) -> PathBuf {
+        self.options
+            .results_dir
+            .join(format!("{}.json", self.start.format("%Y%m%d-%H%M%S")))
+    }
+
+    /// 保存所有已交卷考生的成绩：持锁时只生成内容，释放锁后再写文件，以免阻塞其他连接
+    fn save(&self) {
+        let (revision, json) = {
+            let mut state = self.state.lock().unwrap();
+            state.revision += 1;
+            (state.revision, self.results_json(&state))
+        };
+        let mut written = self.written.lock().unwrap();
+        // 并发保存时已写入了更新的内容
+        if revision <= *written {
+            return;
+        }
+        let path = self.results_path();
+        let saved = fs::create_dir_all(&self.options.results_dir)
+            .map_err(|e| e.to_string())
+            .and(json)
+            .and_then(|json| fs::write(&path, json).map_err(|e| e.to_string()));
+        match saved {
+            Ok(()) => *written = revision,
+            Err(e) => error!("Failed to save results to {}: {e}", path.display()),
+        }
+    }
+
+    /// 成绩文件的内容，有签名密钥时一并签名
+    fn results_json(&self, state: &State) -> Result<String, String> {
+        let results = state
+            .candidates
+            .iter()
+            .filter_map(|(name, c)| {
+                Some(CandidateResult {
+                    name,
+                    score: c.score?,
+                    submitted_at: c.submitted_at?,
+                    paper: &c.paper,
+                    time_spent: c.audit.time_spent(),
+                    audit: &c.audit,
+                })
+            })
+            .collect::<Vec<_>>();
+        let results = Results {
+            exam: self.exam.name(),
+            start: self.start,
+            results,
+        };
+        match &self.options.signing_key {
+            Some(key) => key
+                .sign(results)
+                .map_err(|e| e.to_string())
+                .and_then(|signed| {
+                    serde_json::to_string_pretty(&signed).map_err(|e| e.to_string())
+                }),
+            None => serde_json::to_string_pretty(&results).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::components::examination::{QuestionEnum, QuestionType};
+    use crate::fixture;
+    use crate::server::client::Connection;
+    use crate::server::protocol::{ClientMessage, ServerMessage};
+    use crate::server::{ServeOptions, Server};
+    use ::examination::engine::{AuditEvent, AuditKind, SigningKey};
+    use chrono::TimeDelta;
+    use std::fs;
+    use std::path::Path;
+    use std::sync::Arc;
+    use tokio::net::TcpListener;
+
+    fn quiz(results_dir: &Path) -> Arc<Server> {
+        let options = ServeOptions {
+            results_dir: results_dir.to_path_buf(),
+            signing_key: Some(SigningKey::generate()),
+            ..ServeOptions::default()
+        };
+        Arc::new(Server::new(fixture::exam(), fixture::bank(), options))
+    }
+
+    async fn listen(server: &Arc<Server>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(server.clone().run(listener));
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_loopback() {
+        let results_dir =
+            std::env::temp_dir().join(format!("examination-serve-{}", std::process::id()));
+        let server = quiz(&results_dir);
+        let addr = listen(&server).await;
+
+        let mut alice = Connection::join(&addr, "alice", None).await.unwrap();
+        assert!(matches!(
+            alice.recv().await.unwrap(),
+            Some(ServerMessage::Token(_))
+        ));
+        let Some(ServerMessage::Paper { paper, .. }) = alice.recv().await.unwrap() else {
+            panic!("expected a paper");
+        };
+        // 发给考生的试卷不含答案与解析
+        let judge = paper
+            .iter()
+            .find(|(t, _)| *t == QuestionType::Judge)
+            .unwrap();
+        let QuestionEnum::Judge(judge) = &judge.1[0] else {
+            panic!("expected a judge question");
+        };
+        assert!(judge.answer.is_empty() && judge.explanation.is_none());
+
+        // 同名考生在线时不能再次加入
+        let mut other = Connection::join(&addr, "alice", None).await.unwrap();
+        assert!(matches!(
+            other.recv().await.unwrap(),
+            Some(ServerMessage::Error(_))
+        ));
+
+        // 作答与终端界面一样规范化，不合法的作答被拒绝
+        for (question_type, input) in [
+            (QuestionType::SingleSelect, "b"),
+            (QuestionType::Judge, "y"),
+            (QuestionType::Judge, "maybe"),
+        ] {
+            alice
+                .send(&ClientMessage::Answer {
+                    question_type,
+                    index: 0,
+                    user_input: vec![Some(input.to_string())],
+                })
+                .await
+                .unwrap();
+        }
+        assert!(matches!(
+            alice.recv().await.unwrap(),
+            Some(ServerMessage::Error(_))
+        ));
+        alice.send(&ClientMessage::Submit).await.unwrap();
+        let Some(ServerMessage::Result { score, paper }) = alice.recv().await.unwrap() else {
+            panic!("expected a result");
+        };
+        assert_eq!(score, 2);
+        assert!(format!("{paper:?}").contains("2 < 3"));
+
+        // 成绩文件带有签名
+        let results: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(server.results_path()).unwrap()).unwrap();
+        assert_eq!(results["results"][0]["name"], "alice");
+        let key = server.options.signing_key.as_ref().unwrap();
+        assert!(key.verify(&results).is_ok());
+        fs::remove_dir_all(results_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reconnect() {
+        let results_dir =
+            std::env::temp_dir().join(format!("examination-reconnect-{}", std::process::id()));
+        let server = quiz(&results_dir);
+        let addr = listen(&server).await;
+
+        let mut carol = Connection::join(&addr, "carol", None).await.unwrap();
+        let Some(ServerMessage::Token(token)) = carol.recv().await.unwrap() else {
+            panic!("expected a token");
+        };
+        drop(carol);
+        while server.snapshot()[0].online {
+            tokio::task::yield_now().await;
+        }
+
+        // 离线考生的试卷不能被不带凭证或凭证错误的连接接管
+        for wrong in [None, Some("0".repeat(32))] {
+            let mut other = Connection::join(&addr, "carol", wrong).await.unwrap();
+            assert!(matches!(
+                other.recv().await.unwrap(),
+                Some(ServerMessage::Error(_))
+            ));
+        }
+        let mut carol = Connection::join(&addr, "carol", Some(token.clone()))
+            .await
+            .unwrap();
+        assert!(matches!(
+            carol.recv().await.unwrap(),
+            Some(ServerMessage::Token(t)) if t == token
+        ));
+        assert!(matches!(
+            carol.recv().await.unwrap(),
+            Some(ServerMessage::Paper { .. })
+        ));
+        let _ = fs::remove_dir_all(results_dir);
+    }
+
+    #[tokio::test]
+    async fn test_proctor() {
+        let results_dir =
+            std::env::temp_dir().join(format!("examination-proctor-{}", std::process::id()));
+        let server = quiz(&results_dir);
+        let addr = listen(&server).await;
+
+        let mut bob = Connection::join(&addr, "bob", None).await.unwrap();
+        assert!(matches!(
+            bob.recv().await.unwrap(),
+            Some(ServerMessage::Token(_))
+        ));
+        let Some(ServerMessage::Paper { deadline, .. }) = bob.recv().await.unwrap() else {
+            panic!("expected a paper");
+        };
+        let focus_lost = AuditEvent::now(AuditKind::FocusLost);
+        bob.send(&ClientMessage::Audit(focus_lost)).await.unwrap();
+        for index in [0, 9] {
+            bob.send(&ClientMessage::Answer {
+                question_type: QuestionType::SingleSelect,
+                index,
+                user_input: vec![Some("B".to_string())],
+            })
+            .await
+            .unwrap();
+        }
+        // 收到无效题号的错误时，之前的作答已处理完
+        assert!(matches!(
+            bob.recv().await.unwrap(),
+            Some(ServerMessage::Error(_))
+        ));
+        let status = &server.snapshot()[0];
+        assert_eq!(status.name, "bob");
+        assert!(status.online && status.score.is_none());
+        assert!(
+            status
+                .answered
+                .contains(&(QuestionType::SingleSelect, 1, 1))
+        );
+        assert!(status.answered.contains(&(QuestionType::Judge, 0, 1)));
+        assert_eq!(status.focus_lost, 1);
+        let remaining = status.remaining.unwrap();
+
+        server.extend(5);
+        let Some(ServerMessage::Deadline(extended)) = bob.recv().await.unwrap() else {
+            panic!("expected a new deadline");
+        };
+        assert_eq!((extended - deadline).num_minutes(), 5);
+        let extended_remaining = server.snapshot()[0].remaining.unwrap();
+        assert!(extended_remaining > remaining + TimeDelta::minutes(4));
+
+        server.broadcast("5 minutes left");
+        assert!(matches!(
+            bob.recv().await.unwrap(),
+            Some(ServerMessage::Broadcast(msg)) if msg == "5 minutes left"
+        ));
+
+        // 作答内容已在广播前处理，进度中能看到
+        let status = &server.snapshot()[0];
+        assert_eq!(status.name, "bob");
+        assert!(status.online && status.score.is_none());
+        assert!(
+            status
+                .answered
+                .contains(&(QuestionType::SingleSelect, 1, 1))
+        );
+        assert!(status.answered.contains(&(QuestionType::Judge, 0, 1)));
+
+        server.submit_all();
+        let Some(ServerMessage::Result { score, .. }) = bob.recv().await.unwrap() else {
+            panic!("expected a result");
+        };
+        assert_eq!(score, 2);
+        assert_eq!(server.snapshot()[0].score, Some(2));
+        assert_eq!(server.snapshot()[0].remaining, None);
+        assert!(server.recent_events(1)[0].contains("bob"));
+        // 审计事件附在成绩文件中
+        let results = fs::read_to_string(server.results_path()).unwrap();
+        assert!(results.contains(r#""event": "focus_lost""#));
+        fs::remove_dir_all(results_dir).unwrap();
+    }
+}
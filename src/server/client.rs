@@ -0,0 +1,72 @@
+//! 考生端与考试服务端的连接。
+
+use crate::server::protocol::{self, ClientMessage, ServerMessage};
+use color_eyre::Result;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tokio::io::BufReader;
+use tokio::net::TcpStream;
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+
+pub struct Connection {
+    reader: BufReader<OwnedReadHalf>,
+    writer: OwnedWriteHalf,
+}
+
+impl Connection {
+    /// 连接服务端并以指定姓名加入考试，断线重连时带上此前收到的凭证
+    pub async fn join(addr: &str, name: &str, token: Option<String>) -> Result<Self> {
+        let (reader, writer) = TcpStream::connect(addr).await?.into_split();
+        let mut connection = Self {
+            reader: BufReader::new(reader),
+            writer,
+        };
+        connection
+            .send(&ClientMessage::Hello {
+                name: name.to_string(),
+                token,
+            })
+            .await?;
+        Ok(connection)
+    }
+
+    pub async fn send(&mut self, message: &ClientMessage) -> Result<()> {
+        protocol::send(&mut self.writer, message).await
+    }
+
+    pub async fn recv(&mut self) -> Result<Option<ServerMessage>> {
+        protocol::recv(&mut self.reader).await
+    }
+
+    /// 拆分为读写两端，分别交给不同的任务
+    pub fn into_split(self) -> (BufReader<OwnedReadHalf>, OwnedWriteHalf) {
+        (self.reader, self.writer)
+    }
+}
+
+/// 重连凭证按服务端地址与考生姓名区分
+fn token_key(addr: &str, name: &str) -> String {
+    format!("{name}@{addr}")
+}
+
+/// 读取此前加入该服务端时保存的重连凭证
+pub fn load_token(path: &Path, addr: &str, name: &str) -> Option<String> {
+    let tokens: HashMap<String, String> =
+        serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    tokens.get(&token_key(addr, name)).cloned()
+}
+
+/// 保存服务端发放的重连凭证
+pub fn save_token(path: &Path, addr: &str, name: &str, token: &str) -> Result<()> {
+    let mut tokens: HashMap<String, String> = fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    tokens.insert(token_key(addr, name), token.to_string());
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&tokens)?)?;
+    Ok(())
+}
@@ -0,0 +1,83 @@
+//! 考试服务端与考生客户端之间的消息，每条消息为一行 JSON。
+
+use crate::components::examination::{ExaminationConfig, QuestionType};
+use chrono::{DateTime, Local};
+use color_eyre::Result;
+use examination::engine::AuditEvent;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+pub use examination::engine::Paper;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClientMessage {
+    /// 考生加入；断线重连时带上首次加入时收到的凭证，继续作答原来的试卷
+    Hello {
+        name: String,
+        #[serde(default)]
+        token: Option<String>,
+    },
+    /// 某道题的作答内容，按试卷中的题型与序号定位
+    Answer {
+        question_type: QuestionType,
+        index: usize,
+        user_input: Vec<Option<String>>,
+    },
+    /// 交卷
+    Submit,
+    /// 审计事件，附在成绩中
+    Audit(AuditEvent),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ServerMessage {
+    /// 加入成功后发给考生的重连凭证，考生端保存后在断线重连时出示
+    Token(String),
+    /// 考试尚未开始，到开始时间后再发送试卷
+    Waiting {
+        start: DateTime<Local>,
+    },
+    /// 去掉答案与解析的试卷，断线重连时保留已作答的内容
+    Paper {
+        exam: ExaminationConfig,
+        paper: Paper,
+        deadline: DateTime<Local>,
+    },
+    /// 交卷或到时后的得分，以及带答案与解析的试卷
+    Result {
+        score: u16,
+        paper: Paper,
+    },
+    /// 监考老师延长了考试时间
+    Deadline(DateTime<Local>),
+    /// 监考老师广播的消息
+    Broadcast(String),
+    Error(String),
+}
+
+/// 发送一条消息
+pub async fn send<W, T>(writer: &mut W, message: &T) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes()).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// 接收一条消息，连接关闭时返回 None
+pub async fn recv<R, T>(reader: &mut R) -> Result<Option<T>>
+where
+    R: AsyncBufRead + Unpin,
+    T: DeserializeOwned,
+{
+    let mut line = String::new();
+    if reader.read_line(&mut line).await? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&line)?))
+}
@@ -0,0 +1,325 @@
+//! 主题：界面中的颜色与样式统一通过命名的样式键读取。
+//!
+//! 内置 dark、light、high-contrast、mono 四套主题，通过配置 `"theme"` 选择，
+//! 设置了 `NO_COLOR` 环境变量且未指定主题时使用 mono；`styles` 中的样式键覆盖主题中的同名样式。
+
+use crate::app::Mode;
+use crate::config::Config;
+use ratatui::style::palette::tailwind;
+use ratatui::style::{Color, Modifier, Style};
+use std::collections::HashMap;
+use std::env;
+use std::sync::OnceLock;
+use tracing::error;
+
+pub const DEFAULT_THEME: &str = "dark";
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+static ACCESSIBLE: OnceLock<bool> = OnceLock::new();
+
+#[derive(Clone, Debug, Default)]
+pub struct Theme(HashMap<String, Style>);
+
+impl Theme {
+    /// 内置主题
+    pub fn named(name: &str) -> Option<Self> {
+        let theme = match name {
+            "dark" => Self::dark(),
+            "light" => Self::light(),
+            "high-contrast" => Self::high_contrast(),
+            "mono" => Self::mono(),
+            _ => return None,
+        };
+        Some(theme)
+    }
+
+    /// 按配置选择主题并应用 `styles` 中的覆盖项
+    pub fn from_config(config: &Config, no_color: bool) -> Self {
+        let name = match config.config.theme.as_deref() {
+            Some(name) => name,
+            None if no_color => "mono",
+            None => DEFAULT_THEME,
+        };
+        let mut theme = Self::named(name).unwrap_or_else(|| {
+            error!("Unknown theme: {name}");
+            Self::dark()
+        });
+        // 样式键不区分模式，按固定顺序合并各模式下的配置
+        for mode in [
+            Mode::Examination,
+            Mode::Input,
+            Mode::Alert,
+            Mode::Summary,
+            Mode::Jump,
+            Mode::Help,
+            Mode::Proctor,
+            Mode::Broadcast,
+            Mode::Unlock,
+        ] {
+            if let Some(styles) = config.styles.get(&mode) {
+                for (key, style) in styles {
+                    theme.0.insert(key.clone(), *style);
+                }
+            }
+        }
+        // 无障碍模式下不使用闪烁文字
+        if config.config.accessible {
+            for style in theme.0.values_mut() {
+                *style = style.remove_modifier(Modifier::SLOW_BLINK | Modifier::RAPID_BLINK);
+            }
+        }
+        theme
+    }
+
+    pub fn get(&self, key: &str) -> Style {
+        self.0.get(key).copied().unwrap_or_default()
+    }
+
+    fn set(&mut self, key: &str, style: Style) {
+        self.0.insert(key.to_string(), style);
+    }
+
+    fn dark() -> Self {
+        let mut theme = Self::default();
+        let fg = |color| Style::default().fg(color);
+        let code = Style::default().fg(Color::White).bg(Color::Indexed(235));
+        theme.set("title", fg(Color::Yellow));
+        theme.set("list", fg(Color::Gray));
+        theme.set("highlight", fg(Color::LightBlue));
+        theme.set("popup", fg(Color::Gray).bg(Color::DarkGray));
+        theme.set("popup.key", fg(Color::Yellow));
+        theme.set("alert", fg(Color::Yellow));
+        theme.set("error", fg(Color::LightRed));
+        theme.set("flag", fg(Color::Yellow));
+        theme.set(
+            "timer",
+            fg(Color::Yellow).add_modifier(Modifier::BOLD | Modifier::SLOW_BLINK),
+        );
+        theme.set("navigator.flagged", fg(Color::Black).bg(Color::Yellow));
+        theme.set("navigator.answered", fg(Color::Black).bg(Color::Green));
+        theme.set("navigator.unanswered", fg(Color::Gray));
+        theme.set("question", Style::default());
+        theme.set("question.answering", fg(Color::Yellow));
+        theme.set("question.right", fg(Color::Green));
+        theme.set(
+            "question.wrong",
+            fg(Color::Red).add_modifier(Modifier::CROSSED_OUT),
+        );
+        theme.set(
+            "question.reference",
+            fg(Color::Cyan).add_modifier(Modifier::UNDERLINED),
+        );
+        theme.set("code", code);
+        theme.set("code.fence", fg(Color::DarkGray));
+        theme.set(
+            "code.keyword",
+            code.fg(Color::Magenta).add_modifier(Modifier::BOLD),
+        );
+        theme.set("code.string", code.fg(Color::Green));
+        theme.set("code.number", code.fg(Color::Cyan));
+        theme.set("code.comment", code.fg(Color::DarkGray));
+        for (key, palette) in [
+            ("single_select", tailwind::BLUE),
+            ("multi_select", tailwind::EMERALD),
+            ("judge", tailwind::INDIGO),
+            ("fill_in", tailwind::RED),
+            ("group", tailwind::AMBER),
+        ] {
+            theme.set(&format!("type.{key}"), fg(palette.c400));
+            theme.set(
+                &format!("tab.{key}"),
+                fg(tailwind::SLATE.c200).bg(palette.c900),
+            );
+            theme.set(
+                &format!("tab.{key}.selected"),
+                Style::default().bg(palette.c700),
+            );
+        }
+        theme
+    }
+
+    fn light() -> Self {
+        let mut theme = Self::dark();
+        let fg = |color| Style::default().fg(color);
+        let code = Style::default().fg(Color::Black).bg(Color::Indexed(254));
+        theme.set("title", fg(Color::Blue).add_modifier(Modifier::BOLD));
+        theme.set("list", fg(Color::Black));
+        theme.set("highlight", fg(Color::Blue).add_modifier(Modifier::BOLD));
+        theme.set("popup", fg(Color::Black).bg(Color::Indexed(252)));
+        theme.set("popup.key", fg(Color::Blue));
+        theme.set("alert", fg(Color::Red));
+        theme.set("error", fg(Color::Red));
+        theme.set("flag", fg(Color::Magenta));
+        theme.set(
+            "timer",
+            fg(Color::Red).add_modifier(Modifier::BOLD | Modifier::SLOW_BLINK),
+        );
+        theme.set("navigator.unanswered", fg(Color::DarkGray));
+        theme.set("question.answering", fg(Color::Blue));
+        theme.set("question.right", fg(Color::Indexed(28)));
+        theme.set("code", code);
+        theme.set("code.fence", fg(Color::Gray));
+        theme.set(
+            "code.keyword",
+            code.fg(Color::Magenta).add_modifier(Modifier::BOLD),
+        );
+        theme.set("code.string", code.fg(Color::Indexed(28)));
+        theme.set("code.number", code.fg(Color::Blue));
+        theme.set("code.comment", code.fg(Color::Gray));
+        for (key, palette) in [
+            ("single_select", tailwind::BLUE),
+            ("multi_select", tailwind::EMERALD),
+            ("judge", tailwind::INDIGO),
+            ("fill_in", tailwind::RED),
+            ("group", tailwind::AMBER),
+        ] {
+            theme.set(&format!("type.{key}"), fg(palette.c700));
+            theme.set(
+                &format!("tab.{key}"),
+                fg(tailwind::SLATE.c900).bg(palette.c200),
+            );
+            theme.set(
+                &format!("tab.{key}.selected"),
+                fg(tailwind::SLATE.c50).bg(palette.c600),
+            );
+        }
+        theme
+    }
+
+    /// 高对比度：只使用基本色并加粗，弹框为黑底白字
+    fn high_contrast() -> Self {
+        let mut theme = Self::dark();
+        let bold = |color| Style::default().fg(color).add_modifier(Modifier::BOLD);
+        let code = Style::default().fg(Color::White).bg(Color::Black);
+        theme.set("title", bold(Color::White));
+        theme.set("list", Style::default().fg(Color::White));
+        theme.set("highlight", bold(Color::Black).bg(Color::White));
+        theme.set("popup", Style::default().fg(Color::White).bg(Color::Black));
+        theme.set("popup.key", bold(Color::Yellow));
+        theme.set("alert", bold(Color::Yellow));
+        theme.set("error", bold(Color::Red));
+        theme.set("flag", bold(Color::Yellow));
+        theme.set("timer", bold(Color::Yellow));
+        theme.set("navigator.unanswered", Style::default().fg(Color::White));
+        theme.set("question.answering", bold(Color::Yellow));
+        theme.set("question.right", bold(Color::Green));
+        theme.set(
+            "question.wrong",
+            bold(Color::Red).add_modifier(Modifier::CROSSED_OUT),
+        );
+        theme.set(
+            "question.reference",
+            bold(Color::Cyan).add_modifier(Modifier::UNDERLINED),
+        );
+        theme.set("code", code);
+        theme.set("code.fence", Style::default().fg(Color::White));
+        theme.set(
+            "code.keyword",
+            code.fg(Color::Magenta).add_modifier(Modifier::BOLD),
+        );
+        theme.set("code.string", code.fg(Color::Green));
+        theme.set("code.number", code.fg(Color::Cyan));
+        theme.set(
+            "code.comment",
+            code.fg(Color::White).add_modifier(Modifier::ITALIC),
+        );
+        for key in ["single_select", "multi_select", "judge", "fill_in", "group"] {
+            theme.set(&format!("type.{key}"), bold(Color::White));
+            theme.set(&format!("tab.{key}"), Style::default().fg(Color::White));
+            theme.set(
+                &format!("tab.{key}.selected"),
+                bold(Color::Black).bg(Color::White),
+            );
+        }
+        theme
+    }
+
+    /// 无颜色：只用加粗、下划线、反色等修饰区分状态
+    fn mono() -> Self {
+        let mut theme = Self(
+            Self::dark()
+                .0
+                .into_keys()
+                .map(|key| (key, Style::default()))
+                .collect(),
+        );
+        let with = |modifier| Style::default().add_modifier(modifier);
+        theme.set("title", with(Modifier::BOLD));
+        theme.set("highlight", with(Modifier::REVERSED));
+        theme.set("popup.key", with(Modifier::BOLD));
+        theme.set("alert", with(Modifier::BOLD));
+        theme.set("error", with(Modifier::BOLD));
+        theme.set("flag", with(Modifier::BOLD));
+        theme.set("timer", with(Modifier::BOLD));
+        theme.set(
+            "navigator.flagged",
+            with(Modifier::UNDERLINED | Modifier::BOLD),
+        );
+        theme.set("navigator.answered", with(Modifier::BOLD));
+        theme.set("navigator.unanswered", with(Modifier::DIM));
+        theme.set("question.answering", with(Modifier::UNDERLINED));
+        theme.set("question.right", with(Modifier::BOLD));
+        theme.set("question.wrong", with(Modifier::CROSSED_OUT));
+        theme.set("question.reference", with(Modifier::UNDERLINED));
+        theme.set("code.keyword", with(Modifier::BOLD));
+        theme.set("code.comment", with(Modifier::ITALIC));
+        for key in ["single_select", "multi_select", "judge", "fill_in", "group"] {
+            theme.set(&format!("type.{key}"), with(Modifier::BOLD));
+            theme.set(&format!("tab.{key}.selected"), with(Modifier::REVERSED));
+        }
+        theme
+    }
+}
+
+/// 启动时根据配置初始化全局主题，只有第一次调用生效
+pub fn init(config: &Config) {
+    let no_color = env::var("NO_COLOR").is_ok_and(|v| !v.is_empty());
+    let _ = THEME.set(Theme::from_config(config, no_color));
+    let _ = ACCESSIBLE.set(config.config.accessible);
+}
+
+/// 是否启用无障碍模式：对错以文字标出，不使用闪烁，题目列表可切换为纯文本
+pub fn accessible() -> bool {
+    ACCESSIBLE.get().copied().unwrap_or_default()
+}
+
+/// 读取样式键对应的样式，未初始化时使用默认主题
+pub fn style(key: &str) -> Style {
+    THEME.get_or_init(Theme::dark).get(key)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::Mode;
+    use crate::config::{Config, parse_style};
+    use crate::theme::Theme;
+    use ratatui::style::{Color, Modifier, Style};
+
+    #[test]
+    fn test_theme_from_config() {
+        let mut config = Config::default();
+        let mono = Theme::from_config(&config, true);
+        assert_eq!(
+            mono.get("question.right"),
+            Style::default().add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(mono.get("popup").bg, None);
+
+        let dark = Theme::from_config(&config, false);
+        assert_eq!(dark.get("question.right").fg, Some(Color::Green));
+
+        // 显式指定的主题优先于 NO_COLOR，styles 覆盖主题中的样式
+        config.config.theme = Some("light".to_string());
+        config
+            .styles
+            .entry(Mode::Examination)
+            .or_default()
+            .insert("question.right".to_string(), parse_style("bold blue"));
+        let light = Theme::from_config(&config, true);
+        assert_eq!(light.get("question.right"), parse_style("bold blue"));
+        assert_eq!(
+            light.get("popup"),
+            Theme::named("light").unwrap().get("popup")
+        );
+    }
+}